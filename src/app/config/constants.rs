@@ -8,4 +8,5 @@ pub mod ui {
 pub mod model {
     pub const ZERO_POSITION: usize = 0;
     pub const NUM_COLUMNS: usize = 3;
+    pub const PAGE_STEP: usize = 25;
 }
@@ -1,11 +1,14 @@
 use crossterm::cursor::Show;
-use crossterm::event::DisableMouseCapture;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use crossterm::execute;
 use crossterm::terminal::{disable_raw_mode, LeaveAlternateScreen};
-use ratatui::crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use ratatui::crossterm::event::{self, Event, KeyCode, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
 use ratatui::prelude::*;
-use std::io::{self, stdout, Stdout};
-use std::time::Duration;
+use std::fs::{self, OpenOptions};
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 pub mod config;
 pub mod model;
@@ -14,45 +17,118 @@ pub mod test_utils;
 pub mod ui;
 pub mod utils;
 
+use crate::app::config::constants::model::PAGE_STEP;
+use crate::app::model::chooser::Chooser;
 use crate::app::model::clipboard::ClipboardAction;
+use crate::app::model::notification::Notification;
 use crate::app::state::file_managment::DeleteMode;
-use crate::app::state::{Bookmarks, FileManager, HintBar, Mark, Mode, Navigation, Search};
+use crate::app::state::{
+    Bookmarks, CommandLine, ConfigReload, ContentSearch, DualPanes, FileManager, Filter, Find,
+    FrecencyJump, FuzzyFinder, GoTo, HintBar, Jumps, Mark, Mode, Mouse, Navigation, OpenWith,
+    Picker, Search, Tabs,
+};
 
-use crate::app::ui::modal::hint_bar::HintBarMode;
-use crate::app::ui::modal::ModalKind;
-use crate::app::utils::config_parser::load_config;
+use crate::app::ui::body::Body;
+use crate::app::ui::modal::hint_bar::{self, HintBarMode};
+use crate::app::ui::modal::{BottomLineModalAction, ModalKind};
+use crate::app::utils::config_parser::keymap::{Action, KeyChord, KeyLookup, KeymapMode};
+use crate::app::utils::config_parser::{load_config, Args};
+use crate::app::utils::fs::expand_path;
+use crate::app::utils::i18n::Lang;
 
 use self::state::State;
 
+const CONFIG_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+/// Entries or preview lines moved per wheel notch
+const SCROLL_STEP: usize = 3;
+
 #[derive(Debug)]
 pub struct App<'a> {
     state: State<'a>,
     exit: bool,
     needs_redraw: bool,
+    last_config_check: Instant,
+    /// Screen size at the last draw, to map mouse events onto the layout
+    area: Rect,
+    mouse_captured: bool,
+    last_click: Option<(Instant, Position)>,
+    /// `--last-dir-file`, written on exit with `Z`
+    last_dir_file: Option<PathBuf>,
+    cd_on_exit: bool,
 }
 
 impl<'a> App<'a> {
-    pub fn new() -> io::Result<Self> {
-        let config = load_config();
+    pub fn new(args: Args) -> io::Result<Self> {
+        let (config, config_error) = load_config(&args);
+        let mut state = State::new(config)?;
+        if let Some(msg) = config_error {
+            state.notification = Notification::Error { msg: msg.into() }.into();
+        }
+        state.chooser = Chooser::new(
+            args.choosefile,
+            args.choosefiles,
+            args.choosedir,
+            args.print0,
+        );
+        if let Some(path) = args.selectfile {
+            let path = expand_path(&path.to_string_lossy(), &state.current_dir);
+            if let Err(e) = state.select_path(&path) {
+                state.notification = Notification::Error {
+                    msg: e.to_string().into(),
+                }
+                .into();
+            }
+        }
 
         Ok(App {
-            state: State::new(config)?,
+            state,
             exit: false,
             needs_redraw: true,
+            last_config_check: Instant::now(),
+            area: Rect::default(),
+            mouse_captured: false,
+            last_click: None,
+            last_dir_file: args.last_dir_file,
+            cd_on_exit: false,
         })
     }
 
-    pub fn run(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> io::Result<()> {
+    pub fn run(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Box<dyn Write>>>,
+    ) -> io::Result<()> {
+        self.sync_mouse_capture()?;
         while !self.exit {
             if event::poll(Duration::from_millis(50))? {
                 self.handle_events()?;
+                if self.state.chooser.as_ref().is_some_and(Chooser::is_done) {
+                    self.exit = true;
+                }
+            }
+            let key_timeout = Duration::from_millis(self.state.config.common.key_timeout);
+            if self.state.pending_keys.is_expired(key_timeout) {
+                self.flush_pending_keys();
+            }
+            if self.last_config_check.elapsed() >= CONFIG_CHECK_INTERVAL {
+                self.last_config_check = Instant::now();
+                if self.state.reload_config_if_changed() {
+                    self.sync_mouse_capture()?;
+                    self.needs_redraw = true;
+                }
+            }
+            if self.state.poll_finder() || self.state.poll_grep() {
+                self.needs_redraw = true;
             }
             if self.needs_redraw {
                 if self.state.from_external_app {
                     terminal.clear()?;
                     self.state.from_external_app = false;
                 }
-                terminal.draw(|f| ui::render(&self.state, f))?;
+                terminal.draw(|f| {
+                    self.area = f.area();
+                    ui::render(&self.state, f);
+                })?;
                 self.needs_redraw = false;
             }
         }
@@ -61,244 +137,479 @@ impl<'a> App<'a> {
 
     pub fn handle_events(&mut self) -> io::Result<()> {
         let event = event::read()?;
+        self.handle_event(event)
+    }
+
+    fn handle_event(&mut self, event: Event) -> io::Result<()> {
         if let Event::Resize(_, _) = event {
             self.needs_redraw = true;
             return Ok(());
         }
+        if let Event::Mouse(mouse) = event {
+            self.handle_mouse(mouse);
+            return Ok(());
+        }
         if let Event::Key(key) = event {
-            match self.state.mode {
-                Mode::Normal | Mode::Search => {
-                    if let ModalKind::HintBar { mode } = &self.state.modal_type {
-                        match mode {
-                            HintBarMode::Bookmarks => match key.code {
-                                KeyCode::Char('b') => {
-                                    self.state.enter_bookmarks_mode();
-                                    self.needs_redraw = true;
-                                }
-                                KeyCode::Char('a') => {
-                                    self.state.add_to_bookmarks();
-                                    self.needs_redraw = true;
-                                }
-                                KeyCode::Char('q') => {
-                                    self.state.hide_hint_bar();
-                                    self.needs_redraw = true;
-                                }
-                                KeyCode::Esc => {
-                                    self.state.hide_hint_bar();
-                                    self.needs_redraw = true;
-                                }
-                                _ => {}
-                            },
-                            HintBarMode::Delete => match key.code {
-                                KeyCode::Char('d') => {
-                                    self.state.copy_files(ClipboardAction::Cut);
-                                    self.needs_redraw = true;
-                                }
-                                KeyCode::Char('D') => {
-                                    self.state.delete_files(DeleteMode::Trash);
-                                    self.needs_redraw = true;
-                                }
-                                KeyCode::Char('x') => {
-                                    self.state.delete_files(DeleteMode::Permanent);
-                                    self.needs_redraw = true;
-                                }
-                                KeyCode::Char('q') => {
-                                    self.state.hide_hint_bar();
-                                    self.needs_redraw = true;
-                                }
-                                KeyCode::Esc => {
-                                    self.state.hide_hint_bar();
-                                    self.needs_redraw = true;
-                                }
-                                _ => {}
-                            },
-                            HintBarMode::Exit => match key.code {
-                                KeyCode::Char('z') | KeyCode::Char('Z') => {
-                                    self.state.switch_to_current_dir();
-                                    self.exit = true;
-                                }
-                                KeyCode::Char('q') | KeyCode::Char('Q') => {
-                                    self.exit = true;
-                                }
-                                KeyCode::Esc => {
-                                    self.state.hide_hint_bar();
-                                    self.needs_redraw = true;
-                                }
-                                _ => {}
-                            },
-                        }
-                    } else {
-                        match key.code {
-                            KeyCode::Char('q') => {
-                                self.exit = true;
-                            }
-                            KeyCode::Char('k') | KeyCode::Up => {
-                                let _ = self.state.navigate_up(1);
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('j') | KeyCode::Down => {
-                                let _ = self.state.navigate_down(1);
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('h') | KeyCode::Left => {
-                                if key.modifiers.contains(KeyModifiers::CONTROL) {
-                                    self.state.toggle_hidden_files();
-                                } else {
-                                    let _ = self.state.navigate_to_parent();
-                                }
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('l') | KeyCode::Right => {
-                                let _ = self.state.navigate_to_child_or_exec();
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('r') => {
-                                self.state.rename_file();
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('a') => {
-                                self.state.add_file();
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('y') => {
-                                self.state.copy_files(ClipboardAction::Copy);
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('p') => {
-                                let _ = self.state.paste_files();
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('v') => {
-                                self.state.enter_visual_mode();
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char(' ') => {
-                                self.state.mark_and_down();
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('b') => {
-                                self.state.open_hint_bar(HintBarMode::Bookmarks);
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('Z') | KeyCode::Char('z') => {
-                                self.state.open_hint_bar(HintBarMode::Exit);
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('d') => {
-                                if key.modifiers.contains(KeyModifiers::CONTROL) {
-                                    let _ = self.state.navigate_down(25);
-                                } else {
-                                    self.state.open_hint_bar(HintBarMode::Delete);
-                                }
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('u') => {
-                                if key.modifiers.contains(KeyModifiers::CONTROL) {
-                                    let _ = self.state.navigate_up(25);
-                                }
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('/') => {
-                                self.state.search();
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Esc => {
-                                self.state.exit_search_mode();
-                                self.needs_redraw = true;
-                            }
-                            KeyCode::Char('n') => {
-                                if self.state.mode == Mode::Search {
-                                    if key.modifiers.contains(KeyModifiers::SHIFT) {
-                                        self.state.next_match("prev".to_string());
-                                    } else {
-                                        self.state.next_match("next".to_string());
-                                    }
-                                    self.needs_redraw = true;
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-                Mode::Insert => match key.code {
-                    KeyCode::Enter => {
-                        if self.state.modal_type.is_underline() {
-                            self.state.commit_changes();
-                        }
-                        if self.state.modal_type.is_bottom_line() {
-                            self.state.commit_search();
-                        }
-                        self.needs_redraw = true;
-                    }
-                    KeyCode::Esc => {
-                        self.state.enter_normal_mode();
-                        self.needs_redraw = true;
-                    }
-                    _ => {
-                        if self.state.input.lines().join("").len() < 255 {
-                            self.state.input.input(event);
-                            self.needs_redraw = true;
-                        }
-                    }
-                },
-                Mode::Visual { .. } => match key.code {
-                    KeyCode::Char('k') | KeyCode::Up => {
-                        let _ = self.state.navigate_up(1);
-                        self.needs_redraw = true;
-                    }
-                    KeyCode::Char('j') | KeyCode::Down => {
-                        let _ = self.state.navigate_down(1);
-                        self.needs_redraw = true;
-                    }
-                    KeyCode::Char('[') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        self.state.enter_normal_mode();
-                        self.needs_redraw = true;
-                    }
-                    KeyCode::Esc => {
-                        self.state.enter_normal_mode();
-                        self.needs_redraw = true;
-                    }
-                    KeyCode::Char('v') => {
-                        self.state.enter_normal_mode();
+            let mode = self.state.keymap_mode();
+            let chord = KeyChord::from(key);
+
+            if matches!(
+                mode,
+                KeymapMode::Insert | KeymapMode::CommandLine | KeymapMode::Finder
+            ) {
+                match self.state.keymap.lookup(mode, &[chord]) {
+                    KeyLookup::Action(action)
+                    | KeyLookup::Pending {
+                        exact: Some(action),
+                    } => self.run_action(action, None),
+                    // outside the completing prompts Tab would put whitespace into names
+                    _ if matches!(chord.code, KeyCode::Tab | KeyCode::BackTab) => {}
+                    _ if self.state.input.lines().join("").len() < 255 => {
+                        self.state.input.input(event);
+                        self.state.update_finder_query();
+                        self.state.update_search_query();
                         self.needs_redraw = true;
                     }
                     _ => {}
-                },
-                Mode::Bookmarks { .. } => match key.code {
-                    KeyCode::Char('q') => {
-                        self.state.enter_normal_mode();
-                        self.needs_redraw = true;
-                    }
-                    KeyCode::Esc => {
-                        self.state.enter_normal_mode();
-                        self.needs_redraw = true;
+                }
+                return Ok(());
+            }
+
+            if self.is_count_digit(mode, chord) {
+                if let KeyCode::Char(c) = chord.code {
+                    let digit = c.to_digit(10).unwrap_or_default() as usize;
+                    self.state.pending_keys.push_digit(digit);
+                    self.needs_redraw = true;
+                }
+                return Ok(());
+            }
+
+            self.state.pending_keys.push_key(chord);
+            match self
+                .state
+                .keymap
+                .lookup(mode, &self.state.pending_keys.keys)
+            {
+                KeyLookup::Action(action) => {
+                    self.state.pending_keys.clear_keys();
+                    let count = self.state.pending_keys.take_count();
+                    self.run_action(action, count);
+                }
+                KeyLookup::Pending { .. } => {}
+                // the prefix ends here: run what it binds, then take the key on its own
+                KeyLookup::None if self.state.pending_keys.keys.len() > 1 => {
+                    self.state.pending_keys.keys.pop();
+                    self.flush_pending_keys();
+                    return self.handle_event(event);
+                }
+                KeyLookup::None => self.state.pending_keys.clear(),
+            }
+            self.needs_redraw = true;
+        }
+        Ok(())
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let position = Position::new(mouse.column, mouse.row);
+        if let ModalKind::HintBar { mode } = &self.state.modal_type {
+            if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+                if let Some(action) = hint_bar::action_at(self.area, mode, mouse.row) {
+                    let count = self.state.pending_keys.take_count();
+                    self.run_action(action, count);
+                }
+            }
+            return;
+        }
+        // modals, the bookmark list and the dual-pane layout are keyboard only
+        let columns_shown = self.state.modal_type.is_disabled()
+            && matches!(self.state.mode, Mode::Normal | Mode::Search)
+            && self.state.dual_pane.is_none();
+        if !columns_shown {
+            return;
+        }
+
+        let body = ui::body_area(self.area);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let double = self.last_click.is_some_and(|(at, last)| {
+                    last == position && at.elapsed() < DOUBLE_CLICK_INTERVAL
+                });
+                // a third click starts a new double-click
+                self.last_click = (!double).then(|| (Instant::now(), position));
+                if let Some((column, index)) = Body::entry_at(&self.state, body, position) {
+                    self.state.pending_keys.clear();
+                    if let Err(e) = self.state.click_entry(column, index, double) {
+                        self.state.notification = Notification::Error {
+                            msg: e.to_string().into(),
+                        }
+                        .into();
                     }
-                    KeyCode::Char('k') | KeyCode::Up => {
+                }
+            }
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+                let down = mouse.kind == MouseEventKind::ScrollDown;
+                let over_preview =
+                    Body::layout(body)[2].contains(position) && self.state.files[2].is_empty();
+                if over_preview {
+                    let step = SCROLL_STEP as isize;
+                    self.state.scroll_preview(if down { step } else { -step });
+                } else if body.contains(position) {
+                    let _ = if down {
+                        self.state.navigate_down(SCROLL_STEP)
+                    } else {
+                        self.state.navigate_up(SCROLL_STEP)
+                    };
+                }
+            }
+            _ => return,
+        }
+        self.needs_redraw = true;
+    }
+
+    fn is_count_digit(&self, mode: KeymapMode, chord: KeyChord) -> bool {
+        let counts_allowed = matches!(
+            mode,
+            KeymapMode::Normal | KeymapMode::Visual | KeymapMode::Bookmarks
+        );
+        let is_digit = match chord.code {
+            KeyCode::Char('0') => self.state.pending_keys.count.is_some(),
+            KeyCode::Char(c) => c.is_ascii_digit(),
+            _ => false,
+        };
+        // an explicit binding on a digit wins over the count prefix
+        counts_allowed
+            && is_digit
+            && chord.modifiers.is_empty()
+            && self.state.pending_keys.keys.is_empty()
+            && self.state.keymap.lookup(mode, &[chord]) == KeyLookup::None
+    }
+
+    fn flush_pending_keys(&mut self) {
+        let mode = self.state.keymap_mode();
+        let lookup = self
+            .state
+            .keymap
+            .lookup(mode, &self.state.pending_keys.keys);
+        self.state.pending_keys.clear_keys();
+        let count = self.state.pending_keys.take_count();
+        if let KeyLookup::Pending {
+            exact: Some(action),
+        } = lookup
+        {
+            self.run_action(action, count);
+        }
+        self.needs_redraw = true;
+    }
+
+    fn run_action(&mut self, action: Action, count: Option<usize>) {
+        let times = count.unwrap_or(1);
+        match action {
+            Action::Quit => {
+                self.exit = true;
+                return;
+            }
+            Action::QuitToCurrentDir => {
+                // without the shell wrapper, fall back to a shell started in place
+                if self.last_dir_file.is_some() {
+                    self.cd_on_exit = true;
+                } else {
+                    // the shell replaces this process, so nothing runs after it
+                    let _ = self.save_frecency();
+                    self.state.switch_to_current_dir();
+                }
+                self.exit = true;
+                return;
+            }
+            Action::MoveUp => {
+                if let ModalKind::OpenWith { .. } = self.state.modal_type {
+                    self.state.open_with_move(-(times as isize));
+                } else if self.state.modal_type.is_jumps() {
+                    self.state.jumps_move(-(times as isize));
+                } else if self.state.modal_type.is_finder() {
+                    self.state.finder_move(-(times as isize));
+                } else if self.state.modal_type.is_grep() {
+                    self.state.grep_move(-(times as isize));
+                } else if let Mode::Bookmarks { .. } = self.state.mode {
+                    for _ in 0..times {
                         let _ = self.state.bookmarks_nagivate_up();
-                        self.needs_redraw = true;
                     }
-                    KeyCode::Char('j') | KeyCode::Down => {
+                } else {
+                    let _ = self.state.navigate_up(times);
+                }
+            }
+            Action::MoveDown => {
+                if let ModalKind::OpenWith { .. } = self.state.modal_type {
+                    self.state.open_with_move(times as isize);
+                } else if self.state.modal_type.is_jumps() {
+                    self.state.jumps_move(times as isize);
+                } else if self.state.modal_type.is_finder() {
+                    self.state.finder_move(times as isize);
+                } else if self.state.modal_type.is_grep() {
+                    self.state.grep_move(times as isize);
+                } else if let Mode::Bookmarks { .. } = self.state.mode {
+                    for _ in 0..times {
                         let _ = self.state.bookmarks_nagivate_down();
-                        self.needs_redraw = true;
                     }
-                    KeyCode::Char('d') => {
-                        self.state.delete_from_bookmarks();
-                        self.needs_redraw = true;
+                } else {
+                    let _ = self.state.navigate_down(times);
+                }
+            }
+            Action::PageUp => {
+                let step = times.saturating_mul(PAGE_STEP);
+                if self.state.modal_type.is_grep() {
+                    self.state.grep_move(-(step as isize));
+                } else {
+                    let _ = self.state.navigate_up(step);
+                }
+            }
+            Action::PageDown => {
+                let step = times.saturating_mul(PAGE_STEP);
+                if self.state.modal_type.is_grep() {
+                    self.state.grep_move(step as isize);
+                } else {
+                    let _ = self.state.navigate_down(step);
+                }
+            }
+            Action::Parent => {
+                for _ in 0..times {
+                    let _ = self.state.navigate_to_parent();
+                }
+            }
+            Action::Open => {
+                if let Mode::Bookmarks { .. } = self.state.mode {
+                    let _ = self.state.open_dir_from_bookmark();
+                } else {
+                    let _ = self.state.navigate_to_child_or_exec();
+                }
+            }
+            Action::Rename => self.state.rename_file(),
+            Action::Add => self.state.add_file(),
+            Action::Yank => {
+                self.mark_counted(count);
+                self.state.copy_files(ClipboardAction::Copy);
+            }
+            Action::Cut => {
+                self.mark_counted(count);
+                self.state.copy_files(ClipboardAction::Cut);
+            }
+            Action::Paste => {
+                let _ = self.state.paste_files();
+            }
+            Action::Trash => {
+                self.mark_counted(count);
+                self.state.delete_files(DeleteMode::Trash);
+            }
+            Action::Delete => {
+                self.mark_counted(count);
+                self.state.delete_files(DeleteMode::Permanent);
+            }
+            Action::ToggleVisual => {
+                if let Mode::Visual { .. } = self.state.mode {
+                    self.state.enter_normal_mode();
+                } else {
+                    self.state.enter_visual_mode();
+                }
+            }
+            Action::ToggleMark => {
+                for _ in 0..times {
+                    self.state.mark_and_down();
+                }
+            }
+            Action::ToggleHidden => self.state.toggle_hidden_files(),
+            Action::Search => self.state.search(),
+            Action::SearchNext => {
+                if self.state.mode == Mode::Search {
+                    for _ in 0..times {
+                        self.state.next_match("next".to_string());
                     }
-                    KeyCode::Char('l') => {
-                        let _ = self.state.open_dir_from_bookmark();
-                        self.needs_redraw = true;
+                }
+            }
+            Action::SearchPrev => {
+                if self.state.mode == Mode::Search {
+                    for _ in 0..times {
+                        self.state.next_match("prev".to_string());
                     }
-                    KeyCode::Enter => {
-                        let _ = self.state.open_dir_from_bookmark();
-                        self.needs_redraw = true;
+                }
+            }
+            Action::Cancel => {
+                self.state.pending_keys.clear();
+                match self.state.mode {
+                    _ if self.state.modal_type.is_hint_bar() => self.state.hide_hint_bar(),
+                    _ if self.state.modal_type.is_open_with() => self.state.close_open_with(),
+                    _ if self.state.modal_type.is_jumps() => self.state.close_jumps(),
+                    _ if self.state.modal_type.is_finder() => self.state.close_finder(),
+                    _ if self.state.modal_type.is_grep() => self.state.close_grep(),
+                    _ if self.state.search_origin.is_some() => self.state.cancel_search(),
+                    Mode::Normal if self.state.find.is_some() => self.state.close_find(),
+                    Mode::Normal | Mode::Search => self.state.exit_search_mode(),
+                    _ => self.state.enter_normal_mode(),
+                }
+            }
+            Action::Confirm => match &self.state.modal_type {
+                ModalKind::UnderLine { .. } => self.state.commit_changes(),
+                ModalKind::OpenWith { .. } => self.state.commit_open_with(),
+                ModalKind::Jumps { .. } => self.state.commit_jump(),
+                ModalKind::Finder { .. } => self.state.commit_finder(),
+                ModalKind::Grep { .. } => self.state.open_grep_hit(),
+                ModalKind::BottomLine {
+                    action: BottomLineModalAction::OpenWith { .. },
+                } => self.state.commit_open_with_command(),
+                ModalKind::BottomLine {
+                    action: BottomLineModalAction::Search,
+                } => self.state.commit_search(),
+                ModalKind::BottomLine {
+                    action: BottomLineModalAction::GoTo { .. },
+                } => self.state.commit_goto(),
+                ModalKind::BottomLine {
+                    action: BottomLineModalAction::Frecency,
+                } => self.state.commit_z_prompt(),
+                ModalKind::BottomLine {
+                    action: BottomLineModalAction::Filter,
+                } => self.state.commit_filter(),
+                ModalKind::BottomLine {
+                    action: BottomLineModalAction::Grep,
+                } => self.state.commit_grep(),
+                ModalKind::BottomLine {
+                    action: BottomLineModalAction::Command,
+                } => {
+                    if let Some(action) = self.state.commit_command() {
+                        return self.run_action(action, None);
                     }
-                    _ => {}
-                },
+                }
+                _ => {}
+            },
+            Action::CommandLine => self.state.open_command_line(),
+            Action::Complete | Action::CompletePrev => match self.state.modal_type {
+                ModalKind::BottomLine {
+                    action: BottomLineModalAction::Command,
+                } if action == Action::Complete => self.state.complete_command(),
+                ModalKind::BottomLine {
+                    action: BottomLineModalAction::GoTo { .. },
+                } => self.state.complete_goto(action == Action::Complete),
+                _ => {}
+            },
+            Action::BookmarksMenu | Action::DeleteMenu | Action::ExitMenu => {
+                let mode = match action {
+                    Action::BookmarksMenu => HintBarMode::Bookmarks,
+                    Action::DeleteMenu => HintBarMode::Delete,
+                    _ => HintBarMode::Exit,
+                };
+                self.state.open_hint_bar(mode);
+                // the count applies to the action picked from the menu, e.g. `3dd`
+                self.state.pending_keys.count = count;
+            }
+            Action::BookmarkList => self.state.enter_bookmarks_mode(),
+            Action::BookmarkAdd => self.state.add_to_bookmarks(),
+            Action::BookmarkDelete => self.state.delete_from_bookmarks(),
+            Action::GoTop => {
+                let _ = self
+                    .state
+                    .navigate_to_position(count.map_or(0, |n| n.saturating_sub(1)));
+            }
+            Action::GoBottom => {
+                let _ = self
+                    .state
+                    .navigate_to_position(count.map_or(usize::MAX, |n| n.saturating_sub(1)));
+            }
+            Action::GoHome => {
+                let home = expand_path("~", &self.state.current_dir);
+                self.change_dir_or_notify(home);
+            }
+            Action::GoRoot => self.change_dir_or_notify(PathBuf::from("/")),
+            Action::OpenWith => self.state.open_with_menu(),
+            Action::JumpBack => self.state.jump_back(times),
+            Action::JumpForward => self.state.jump_forward(times),
+            Action::Jumps => self.state.jumps_menu(),
+            Action::NewTab => {
+                let _ = self.state.new_tab(None);
+            }
+            Action::CloseTab => self.state.close_tab(),
+            // like vim, `3gt` goes to the third tab
+            Action::NextTab => match count {
+                Some(n) => self.state.switch_tab(n.saturating_sub(1)),
+                None => self.state.next_tab(1),
+            },
+            Action::PrevTab => self.state.next_tab(-(times as isize)),
+            Action::MoveTabLeft => self.state.move_tab(-(times as isize)),
+            Action::MoveTabRight => self.state.move_tab(times as isize),
+            Action::GoTo => self.state.open_goto_prompt(),
+            Action::Finder => self.state.open_finder(),
+            Action::ZJump => self.state.open_z_prompt(),
+            Action::Filter => self.state.open_filter(),
+            Action::Grep => self.state.open_grep_prompt(),
+            Action::ClearFilter => self.state.clear_filter(),
+            Action::Choose => {
+                let _ = self.state.confirm_choice();
+            }
+            Action::ToggleDualPane => self.state.toggle_dual_pane(),
+            Action::SwitchPane => self.state.switch_pane(),
+            Action::CopyToPane => {
+                self.mark_counted(count);
+                self.state.transfer_to_other_pane(ClipboardAction::Copy);
+            }
+            Action::MoveToPane => {
+                self.mark_counted(count);
+                self.state.transfer_to_other_pane(ClipboardAction::Cut);
             }
         }
+        self.needs_redraw = true;
+    }
+
+    fn mark_counted(&mut self, count: Option<usize>) {
+        if let Some(count) = count {
+            if self.state.marked.is_empty() {
+                self.state.mark_next(count);
+            }
+        }
+    }
+
+    /// Writes the picker outputs; called once the terminal is restored.
+    pub fn write_choice(&self) -> io::Result<()> {
+        match &self.state.chooser {
+            Some(chooser) => chooser.write(&self.state.current_dir),
+            None => Ok(()),
+        }
+    }
+
+    /// Leaves `--last-dir-file` empty unless quitting with `Z`.
+    pub fn write_last_dir(&self) -> io::Result<()> {
+        match &self.last_dir_file {
+            Some(file) if self.cd_on_exit => {
+                fs::write(file, self.state.current_dir.as_os_str().as_encoded_bytes())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Writes the visited directories gathered this session.
+    pub fn save_frecency(&mut self) -> io::Result<()> {
+        self.state
+            .frecency
+            .save()
+            .map_err(|e| io::Error::other(Lang::en_fmt("frecency_save_failed", &[&e.to_string()])))
+    }
+
+    fn sync_mouse_capture(&mut self) -> io::Result<()> {
+        let wanted = self.state.config.common.mouse;
+        if wanted != self.mouse_captured {
+            if wanted {
+                execute!(tui_output(), EnableMouseCapture)?;
+            } else {
+                execute!(tui_output(), DisableMouseCapture)?;
+            }
+            self.mouse_captured = wanted;
+        }
         Ok(())
     }
+
+    fn change_dir_or_notify(&mut self, dir: PathBuf) {
+        if let Err(e) = self.state.change_dir(dir) {
+            self.state.notification = Notification::Error {
+                msg: e.to_string().into(),
+            }
+            .into();
+        }
+    }
 }
 
 impl Drop for App<'_> {
@@ -311,6 +622,92 @@ impl Drop for App<'_> {
 
 pub fn cleanup_terminal() -> io::Result<()> {
     disable_raw_mode().map_err(io::Error::other)?;
-    execute!(stdout(), LeaveAlternateScreen, DisableMouseCapture, Show)?;
+    execute!(
+        tui_output(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        Show
+    )?;
     Ok(())
 }
+
+/// Where the interface is drawn: stdout, or the terminal itself when stdout
+/// is captured, as in `$(stranger --choosefile -)`.
+pub fn tui_output() -> Box<dyn Write> {
+    if !io::stdout().is_terminal() {
+        if let Ok(tty) = OpenOptions::new().write(true).open("/dev/tty") {
+            return Box::new(tty);
+        }
+    }
+    Box::new(io::stdout())
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::app::{
+        test_utils::create_test_state,
+        utils::config_parser::keymap::{Keymap, KeysConfig},
+    };
+
+    /// `g` alone quits, and still starts `gg` and the other `g` sequences
+    fn test_app() -> App<'static> {
+        let mut keys = KeysConfig::default();
+        keys.normal.insert("quit".into(), vec!["g".into()]);
+        let mut state = create_test_state();
+        state.keymap = Keymap::from_config(&keys).unwrap();
+        App {
+            state,
+            exit: false,
+            needs_redraw: false,
+            last_config_check: Instant::now(),
+            area: Rect::default(),
+            mouse_captured: false,
+            last_click: None,
+            last_dir_file: None,
+            cd_on_exit: false,
+        }
+    }
+
+    fn press_key(app: &mut App, code: KeyCode) {
+        let key = KeyEvent::new(code, KeyModifiers::NONE);
+        app.handle_event(Event::Key(key)).unwrap();
+    }
+
+    fn press(app: &mut App, c: char) {
+        press_key(app, KeyCode::Char(c));
+    }
+
+    #[test]
+    fn bound_prefix_runs_on_timeout() {
+        let mut app = test_app();
+        press(&mut app, 'g');
+        assert!(!app.exit);
+        app.flush_pending_keys();
+        assert!(app.exit);
+        assert!(app.state.pending_keys.is_empty());
+    }
+
+    #[test]
+    fn unmatched_key_runs_the_prefix_and_then_itself() {
+        let mut app = test_app();
+        press(&mut app, 'g');
+        press(&mut app, ':');
+        assert!(app.exit);
+        assert!(app.state.modal_type.is_bottom_line());
+        assert!(app.state.pending_keys.is_empty());
+    }
+
+    #[test]
+    fn tab_types_nothing_in_plain_prompts() {
+        let mut app = test_app();
+        app.state.search();
+        press(&mut app, 'a');
+        press_key(&mut app, KeyCode::Tab);
+        press_key(&mut app, KeyCode::BackTab);
+        press(&mut app, 'b');
+        assert_eq!(app.state.input.lines(), ["ab"]);
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Output path that stands for stdout
+pub const STDOUT: &str = "-";
+
+/// Picker mode: opening a file records the choice and quits, and the
+/// outputs are written once the terminal is restored.
+#[derive(Debug, Default, PartialEq)]
+pub struct Chooser {
+    /// Receives the opened file
+    pub file: Option<PathBuf>,
+    /// Receives the marked files, or the opened one when nothing is marked
+    pub files: Option<PathBuf>,
+    /// Receives the directory stranger was in on exit
+    pub dir: Option<PathBuf>,
+    pub separator: char,
+    chosen: Option<Vec<PathBuf>>,
+}
+
+impl Chooser {
+    /// `None` unless at least one output is given.
+    pub fn new(
+        file: Option<PathBuf>,
+        files: Option<PathBuf>,
+        dir: Option<PathBuf>,
+        null_separated: bool,
+    ) -> Option<Self> {
+        if file.is_none() && files.is_none() && dir.is_none() {
+            return None;
+        }
+        Some(Chooser {
+            file,
+            files,
+            dir,
+            separator: if null_separated { '\0' } else { '\n' },
+            chosen: None,
+        })
+    }
+
+    /// Whether opening a file picks it instead of launching it.
+    pub fn picks_files(&self) -> bool {
+        self.file.is_some() || self.files.is_some()
+    }
+
+    pub fn picks_many(&self) -> bool {
+        self.files.is_some()
+    }
+
+    pub fn choose(&mut self, paths: Vec<PathBuf>) {
+        if !paths.is_empty() {
+            self.chosen = Some(paths);
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.chosen.is_some()
+    }
+
+    pub fn write(&self, current_dir: &Path) -> io::Result<()> {
+        if let Some(chosen) = &self.chosen {
+            if let Some(output) = &self.file {
+                self.write_paths(output, &chosen[..1])?;
+            }
+            if let Some(output) = &self.files {
+                self.write_paths(output, chosen)?;
+            }
+        }
+        if let Some(output) = &self.dir {
+            self.write_paths(output, &[current_dir.to_path_buf()])?;
+        }
+        Ok(())
+    }
+
+    /// Each path followed by the separator, so the last one is terminated too.
+    fn write_paths(&self, output: &Path, paths: &[PathBuf]) -> io::Result<()> {
+        let mut content = String::new();
+        for path in paths {
+            content.push_str(&path.to_string_lossy());
+            content.push(self.separator);
+        }
+        if output == Path::new(STDOUT) {
+            let mut stdout = io::stdout();
+            stdout.write_all(content.as_bytes())?;
+            stdout.flush()
+        } else {
+            fs::write(output, content)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_choice_to_every_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = |name: &str| dir.path().join(name);
+
+        assert!(Chooser::new(None, None, None, false).is_none());
+        let mut chooser = Chooser::new(
+            Some(out("file")),
+            Some(out("files")),
+            Some(out("dir")),
+            true,
+        )
+        .unwrap();
+        assert!(chooser.picks_files());
+
+        chooser.choose(vec![PathBuf::from("/a/x"), PathBuf::from("/a/y")]);
+        assert!(chooser.is_done());
+        chooser.write(Path::new("/a")).unwrap();
+
+        assert_eq!(fs::read_to_string(out("file")).unwrap(), "/a/x\0");
+        assert_eq!(fs::read_to_string(out("files")).unwrap(), "/a/x\0/a/y\0");
+        assert_eq!(fs::read_to_string(out("dir")).unwrap(), "/a\0");
+    }
+
+    #[test]
+    fn nothing_chosen_writes_only_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let chooser = Chooser::new(
+            Some(dir.path().join("file")),
+            None,
+            Some(dir.path().join("dir")),
+            false,
+        )
+        .unwrap();
+        chooser.write(Path::new("/work")).unwrap();
+        assert!(!dir.path().join("file").exists());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("dir")).unwrap(),
+            "/work\n"
+        );
+    }
+}
@@ -0,0 +1,12 @@
+use crate::app::model::{miller::entries::FileEntry, tabs::Tab};
+
+/// Commander-style layout. The focused pane lives in `State` like a tab on
+/// screen; this holds the other one.
+#[derive(Debug)]
+pub struct DualPane {
+    pub other: Tab,
+    /// Entries of `other.current_dir`, rebuilt along with the columns
+    pub other_files: Vec<FileEntry>,
+    /// Whether the focused pane is drawn on the left
+    pub focus_left: bool,
+}
@@ -1,6 +1,7 @@
 use std::{
     collections::HashMap,
-    fs::Metadata,
+    fs::{self, Metadata},
+    os::unix::fs::{FileTypeExt, PermissionsExt},
     path::{Path, PathBuf},
 };
 
@@ -9,7 +10,7 @@ use clap::Error;
 
 use crate::app::{
     model::miller::{
-        entries::{FileEntry, FileVariant},
+        entries::{FileEntry, FileKind, FileVariant},
         positions::get_position,
     },
     utils::permissions_to_string,
@@ -24,8 +25,9 @@ pub fn get_current_file<'a>(
     files.get(position_id)
 }
 
+/// Where `file` lives; entries of `dir` only carry their name, find results their full path.
 pub fn build_full_path(dir: &Path, file: &FileEntry) -> PathBuf {
-    dir.join(&file.name)
+    file.path.clone().unwrap_or_else(|| dir.join(&file.name))
 }
 
 pub fn calculate_file_size(file_metadata: Metadata) -> u64 {
@@ -44,6 +46,32 @@ pub fn get_last_modified(file_metadata: &Metadata) -> Result<String, Error> {
     Ok(formatted_time)
 }
 
+/// `metadata` must not follow symlinks, `path` is only used to resolve link targets.
+pub fn get_file_kind(path: &Path, metadata: &Metadata) -> FileKind {
+    let file_type = metadata.file_type();
+    if file_type.is_symlink() {
+        if fs::metadata(path).is_ok() {
+            FileKind::Symlink
+        } else {
+            FileKind::Orphan
+        }
+    } else if file_type.is_dir() {
+        FileKind::Directory
+    } else if file_type.is_fifo() {
+        FileKind::Pipe
+    } else if file_type.is_socket() {
+        FileKind::Socket
+    } else if file_type.is_block_device() {
+        FileKind::BlockDevice
+    } else if file_type.is_char_device() {
+        FileKind::CharDevice
+    } else if metadata.permissions().mode() & 0o111 != 0 {
+        FileKind::Executable
+    } else {
+        FileKind::Regular
+    }
+}
+
 pub fn count_dir_entries<P: AsRef<Path>>(path: P) -> u64 {
     if let Ok(path) = std::fs::read_dir(path) {
         let count = path.count();
@@ -67,7 +95,7 @@ pub fn count_matched_files(files: &[FileEntry]) -> usize {
 mod tests {
     use std::path::PathBuf;
 
-    use crate::app::model::miller::entries::{FileEntry, FileVariant};
+    use crate::app::model::miller::entries::{FileEntry, FileKind, FileVariant};
 
     use super::*;
 
@@ -82,6 +110,8 @@ mod tests {
                 last_modified: Some("2023-10-01 12:00".into()),
                 is_matched: false,
             },
+            kind: FileKind::Regular,
+            path: None,
         };
         let path = build_full_path(&dir, &file);
 
@@ -99,6 +129,8 @@ mod tests {
                 last_modified: Some("2023-10-01 12:00".into()),
                 is_matched: false,
             },
+            kind: FileKind::Regular,
+            path: None,
         }];
         let mut positions: HashMap<PathBuf, usize> = HashMap::new();
         positions.insert(dir.clone(), 0);
@@ -113,8 +145,31 @@ mod tests {
                     last_modified: Some("2023-10-01 12:00".into()),
                     is_matched: false,
                 },
+                kind: FileKind::Regular,
+                path: None,
             }),
             current_file,
         );
     }
+
+    #[test]
+    fn file_kinds() {
+        let dir = tempfile::tempdir().unwrap();
+        let kind_of = |name: &str| {
+            let path = dir.path().join(name);
+            get_file_kind(&path, &fs::symlink_metadata(&path).unwrap())
+        };
+
+        fs::write(dir.path().join("plain"), "").unwrap();
+        fs::write(dir.path().join("script"), "").unwrap();
+        fs::set_permissions(dir.path().join("script"), fs::Permissions::from_mode(0o755)).unwrap();
+        std::os::unix::fs::symlink(dir.path().join("plain"), dir.path().join("link")).unwrap();
+        std::os::unix::fs::symlink(dir.path().join("missing"), dir.path().join("broken")).unwrap();
+
+        assert_eq!(kind_of("plain"), FileKind::Regular);
+        assert_eq!(kind_of("script"), FileKind::Executable);
+        assert_eq!(kind_of("link"), FileKind::Symlink);
+        assert_eq!(kind_of("broken"), FileKind::Orphan);
+        assert_eq!(kind_of(""), FileKind::Directory);
+    }
 }
@@ -0,0 +1,257 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::app::{
+    model::{
+        matcher::{MatchEngine, Matcher},
+        miller::{columns::MillerColumns, entries::FileEntry},
+    },
+    utils::{fs::walker, i18n::Lang},
+};
+
+/// The walk stops here; a query this broad is better narrowed down
+const MAX_ENTRIES: usize = 10_000;
+
+/// What a find keeps: a name pattern plus size and age bounds, written as
+/// `*.rs size>10k mtime<2d`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FindQuery {
+    terms: String,
+    name: Option<Matcher>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    /// Modified less than this long ago
+    newer_than: Option<Duration>,
+    /// Modified more than this long ago
+    older_than: Option<Duration>,
+}
+
+impl FindQuery {
+    /// Terms other than `size>N`, `size<N`, `mtime<AGE` and `mtime>AGE` make up
+    /// the name pattern, a glob when it has `*?[` and otherwise matched with `engine`.
+    pub fn parse(terms: &[String], engine: MatchEngine) -> Result<Self, String> {
+        let mut query = FindQuery {
+            terms: terms.join(" "),
+            name: None,
+            min_size: None,
+            max_size: None,
+            newer_than: None,
+            older_than: None,
+        };
+        let invalid = |term: &str| Lang::en_fmt("invalid_find_term", &[term]);
+        let mut names = vec![];
+        for term in terms {
+            if let Some(size) = term.strip_prefix("size>") {
+                query.min_size = Some(parse_size(size).ok_or_else(|| invalid(term))?);
+            } else if let Some(size) = term.strip_prefix("size<") {
+                query.max_size = Some(parse_size(size).ok_or_else(|| invalid(term))?);
+            } else if let Some(age) = term.strip_prefix("mtime<") {
+                query.newer_than = Some(parse_age(age).ok_or_else(|| invalid(term))?);
+            } else if let Some(age) = term.strip_prefix("mtime>") {
+                query.older_than = Some(parse_age(age).ok_or_else(|| invalid(term))?);
+            } else {
+                names.push(term.as_str());
+            }
+        }
+        if !names.is_empty() {
+            let name = names.join(" ");
+            let engine = if name.contains(['*', '?', '[']) {
+                MatchEngine::Glob
+            } else {
+                engine
+            };
+            query.name = Some(Matcher::new(&name, engine)?);
+        }
+        Ok(query)
+    }
+
+    pub fn terms(&self) -> &str {
+        &self.terms
+    }
+
+    /// Size bounds only ever match files.
+    fn matches(&self, name: &str, is_dir: bool, size: u64, modified: Option<SystemTime>) -> bool {
+        if self
+            .name
+            .as_ref()
+            .is_some_and(|matcher| !matcher.is_match(name))
+        {
+            return false;
+        }
+        let sized = self.min_size.is_some() || self.max_size.is_some();
+        if sized
+            && (is_dir
+                || self.min_size.is_some_and(|min| size <= min)
+                || self.max_size.is_some_and(|max| size >= max))
+        {
+            return false;
+        }
+        if self.newer_than.is_none() && self.older_than.is_none() {
+            return true;
+        }
+        let Some(age) = modified.and_then(|time| SystemTime::now().duration_since(time).ok())
+        else {
+            return false;
+        };
+        self.newer_than.is_none_or(|max| age < max) && self.older_than.is_none_or(|min| age > min)
+    }
+}
+
+/// `512`, `10k`, `3M`, `1G`; binary units.
+fn parse_size(size: &str) -> Option<u64> {
+    let (number, unit) = split_unit(size);
+    let unit = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" => 1 << 10,
+        "m" => 1 << 20,
+        "g" => 1 << 30,
+        _ => return None,
+    };
+    number.parse::<u64>().ok()?.checked_mul(unit)
+}
+
+/// `30s`, `15m`, `2h`, `3d`, `1w`; a bare number counts days as `find -mtime` does.
+fn parse_age(age: &str) -> Option<Duration> {
+    let (number, unit) = split_unit(age);
+    let unit = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "" | "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    Some(Duration::from_secs(
+        number.parse::<u64>().ok()?.checked_mul(unit)?,
+    ))
+}
+
+fn split_unit(value: &str) -> (&str, &str) {
+    let digits = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    value.split_at(digits)
+}
+
+/// Results of a recursive find under `root`, listed in place of its entries.
+/// Each entry is named by its path relative to `root` and carries its full path.
+#[derive(Debug)]
+pub struct FindListing {
+    pub root: PathBuf,
+    pub query: FindQuery,
+    pub entries: Vec<FileEntry>,
+    /// Set when the walk stopped at `MAX_ENTRIES`
+    pub truncated: bool,
+    /// Selection in `root` before the find, restored when it is closed
+    pub origin_position: usize,
+}
+
+impl FindListing {
+    pub fn new(root: &Path, query: FindQuery, origin_position: usize) -> Self {
+        FindListing {
+            root: root.to_path_buf(),
+            query,
+            entries: vec![],
+            truncated: false,
+            origin_position,
+        }
+    }
+
+    /// Walks `root` again, skipping hidden entries unless `show_hidden` and
+    /// ignored ones when `respect_ignore`.
+    pub fn rescan(&mut self, show_hidden: bool, respect_ignore: bool) {
+        let walker = walker(&self.root, show_hidden, respect_ignore)
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+
+        self.entries = walker
+            .flatten()
+            .filter(|entry| entry.depth() > 0)
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                let name = entry.file_name().to_string_lossy();
+                let modified = metadata.modified().ok();
+                if !self
+                    .query
+                    .matches(&name, metadata.is_dir(), metadata.len(), modified)
+                {
+                    return None;
+                }
+                let relative = entry.path().strip_prefix(&self.root).ok()?;
+                let mut file = MillerColumns::entry_from_metadata(
+                    relative.to_string_lossy().into_owned(),
+                    entry.path(),
+                    metadata,
+                    true,
+                    false,
+                );
+                file.path = Some(entry.path().to_path_buf());
+                Some(file)
+            })
+            // one past the limit tells whether anything was left out
+            .take(MAX_ENTRIES + 1)
+            .collect();
+        self.truncated = self.entries.len() > MAX_ENTRIES;
+        self.entries.truncate(MAX_ENTRIES);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn find(root: &Path, terms: &[&str]) -> Vec<String> {
+        let terms: Vec<String> = terms.iter().map(|t| t.to_string()).collect();
+        let query = FindQuery::parse(&terms, MatchEngine::Substring).unwrap();
+        let mut listing = FindListing::new(root, query, 0);
+        listing.rescan(false, true);
+        listing.entries.into_iter().map(|e| e.name).collect()
+    }
+
+    #[test]
+    fn finds_by_name_size_and_age() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir_all(root.path().join("src/ui")).unwrap();
+        fs::write(root.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(root.path().join("src/ui/big.rs"), vec![b'x'; 4096]).unwrap();
+        fs::write(root.path().join("notes.txt"), "").unwrap();
+
+        assert_eq!(
+            find(root.path(), &["*.rs"]),
+            ["src/main.rs", "src/ui/big.rs"]
+        );
+        assert_eq!(find(root.path(), &["ui"]), ["src/ui"]);
+        assert_eq!(find(root.path(), &["size>1k"]), ["src/ui/big.rs"]);
+        assert_eq!(
+            find(root.path(), &["size<1k", "mtime<1d"]),
+            ["notes.txt", "src/main.rs"]
+        );
+        assert!(find(root.path(), &["mtime>1w"]).is_empty());
+
+        let listing = {
+            let query = FindQuery::parse(&["big".into()], MatchEngine::Substring).unwrap();
+            let mut listing = FindListing::new(root.path(), query, 0);
+            listing.rescan(false, true);
+            listing
+        };
+        assert_eq!(
+            listing.entries[0].path.as_deref(),
+            Some(root.path().join("src/ui/big.rs").as_path())
+        );
+    }
+
+    #[test]
+    fn parses_units() {
+        assert_eq!(parse_size("10k"), Some(10 * 1024));
+        assert_eq!(parse_size("2M"), Some(2 << 20));
+        assert_eq!(parse_size("7"), Some(7));
+        assert_eq!(parse_size("k"), None);
+        assert_eq!(parse_age("2h"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_age("1"), Some(Duration::from_secs(86400)));
+        assert!(FindQuery::parse(&["size>lots".into()], MatchEngine::Substring).is_err());
+    }
+}
@@ -0,0 +1,228 @@
+use std::{
+    cmp::Reverse,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+
+use crate::app::utils::fs::walker;
+
+/// Entries taken from the walker per poll, so a huge tree cannot stall a frame.
+const POLL_BATCH: usize = 5000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinderEntry {
+    /// Path relative to the finder root, `/`-separated
+    pub path: String,
+    pub is_dir: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinderMatch {
+    /// Index into `Finder::entries`
+    pub entry: usize,
+    pub score: i64,
+    /// Char positions of `path` that matched the query
+    pub indices: Vec<usize>,
+}
+
+/// Fuzzy finder over the tree under `root`. Paths stream in from a walker
+/// thread and are ranked against the query as they arrive.
+#[derive(Debug)]
+pub struct Finder {
+    pub root: PathBuf,
+    pub entries: Vec<FinderEntry>,
+    /// Ranked best first
+    pub matches: Vec<FinderMatch>,
+    pub selected: usize,
+    query: String,
+    /// Dropped once the walk is over; dropping it early stops the walker
+    receiver: Option<Receiver<FinderEntry>>,
+}
+
+impl Finder {
+    /// Starts walking `root`, skipping hidden entries unless `show_hidden` and
+    /// ignored ones when `respect_ignore`.
+    pub fn spawn(root: &Path, show_hidden: bool, respect_ignore: bool) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let walker = walker(root, show_hidden, respect_ignore).build();
+        let walk_root = root.to_path_buf();
+
+        thread::spawn(move || {
+            for entry in walker.flatten() {
+                if entry.depth() == 0 {
+                    continue;
+                }
+                let Ok(relative) = entry.path().strip_prefix(&walk_root) else {
+                    continue;
+                };
+                let entry = FinderEntry {
+                    path: relative.to_string_lossy().into_owned(),
+                    is_dir: entry.file_type().is_some_and(|t| t.is_dir()),
+                };
+                // the finder was closed
+                if sender.send(entry).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Finder {
+            root: root.to_path_buf(),
+            entries: vec![],
+            matches: vec![],
+            selected: 0,
+            query: String::new(),
+            receiver: Some(receiver),
+        }
+    }
+
+    pub fn is_walking(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Takes the paths found since the last call. Returns whether anything changed.
+    pub fn poll(&mut self) -> bool {
+        let Some(receiver) = &self.receiver else {
+            return false;
+        };
+        let from = self.entries.len();
+        let mut finished = false;
+        while self.entries.len() - from < POLL_BATCH {
+            match receiver.try_recv() {
+                Ok(entry) => self.entries.push(entry),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+        if finished {
+            self.receiver = None;
+        }
+        if self.entries.len() > from {
+            self.rank(from);
+        }
+        finished || self.entries.len() > from
+    }
+
+    pub fn set_query(&mut self, query: &str) {
+        if self.query == query {
+            return;
+        }
+        self.query = query.to_string();
+        self.matches.clear();
+        self.selected = 0;
+        self.rank(0);
+    }
+
+    pub fn move_selection(&mut self, step: isize) {
+        let last = self.matches.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(step).min(last);
+    }
+
+    pub fn selected_entry(&self) -> Option<&FinderEntry> {
+        self.matches
+            .get(self.selected)
+            .map(|m| &self.entries[m.entry])
+    }
+
+    /// Scores `entries[from..]` and merges them into `matches`. With an empty
+    /// query everything matches, in walk order.
+    fn rank(&mut self, from: usize) {
+        let matcher = SkimMatcherV2::default().smart_case();
+        let query = self.query.trim();
+        for (id, entry) in self.entries.iter().enumerate().skip(from) {
+            let found = if query.is_empty() {
+                Some((0, vec![]))
+            } else {
+                matcher.fuzzy_indices(&entry.path, query)
+            };
+            if let Some((score, indices)) = found {
+                self.matches.push(FinderMatch {
+                    entry: id,
+                    score,
+                    indices,
+                });
+            }
+        }
+        let entries = &self.entries;
+        // shorter paths first among equal scores, they are usually what was meant
+        self.matches
+            .sort_by_key(|m| (Reverse(m.score), entries[m.entry].path.len(), m.entry));
+        self.selected = self.selected.min(self.matches.len().saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, thread, time::Duration};
+
+    use super::*;
+
+    fn walk(root: &Path, show_hidden: bool, respect_ignore: bool) -> Finder {
+        let mut finder = Finder::spawn(root, show_hidden, respect_ignore);
+        while finder.is_walking() {
+            finder.poll();
+            thread::sleep(Duration::from_millis(5));
+        }
+        finder
+    }
+
+    fn paths(finder: &Finder) -> Vec<&str> {
+        finder
+            .matches
+            .iter()
+            .map(|m| finder.entries[m.entry].path.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn walks_honouring_hidden_and_ignore() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir_all(root.path().join("src/ui")).unwrap();
+        fs::create_dir(root.path().join("target")).unwrap();
+        fs::write(root.path().join("src/ui/widget.rs"), "").unwrap();
+        fs::write(root.path().join("target/out.rs"), "").unwrap();
+        fs::write(root.path().join(".env"), "").unwrap();
+        fs::write(root.path().join(".ignore"), "target\n").unwrap();
+
+        let finder = walk(root.path(), false, true);
+        let mut found = paths(&finder);
+        found.sort();
+        assert_eq!(found, ["src", "src/ui", "src/ui/widget.rs"]);
+
+        let finder = walk(root.path(), true, false);
+        assert!(paths(&finder).contains(&".env"));
+        assert!(paths(&finder).contains(&"target/out.rs"));
+    }
+
+    #[test]
+    fn ranks_and_highlights_matches() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir_all(root.path().join("src/ui/body")).unwrap();
+        fs::write(root.path().join("src/ui/body/column_widget.rs"), "").unwrap();
+        fs::write(root.path().join("src/columns.rs"), "").unwrap();
+        fs::write(root.path().join("README.md"), "").unwrap();
+
+        let mut finder = walk(root.path(), false, false);
+        finder.set_query("colwid");
+        assert_eq!(paths(&finder), ["src/ui/body/column_widget.rs"]);
+        assert_eq!(finder.matches[0].indices.len(), 6);
+
+        finder.set_query("col");
+        assert_eq!(paths(&finder).len(), 2);
+        finder.move_selection(5);
+        assert_eq!(finder.selected, 1);
+
+        finder.set_query("zzz");
+        assert!(finder.selected_entry().is_none());
+    }
+}
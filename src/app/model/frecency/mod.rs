@@ -0,0 +1,194 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+/// Once the ranks add up past this, all of them decay and the rarely
+/// visited directories drop out
+const MAX_TOTAL_RANK: f64 = 10_000.0;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct Visits {
+    pub rank: f64,
+    /// Seconds since the Unix epoch
+    pub last_visit: u64,
+}
+
+impl Visits {
+    /// Visits weighted by how long ago the last one was.
+    pub fn score(&self, now: u64) -> f64 {
+        let factor = match now.saturating_sub(self.last_visit) {
+            age if age < HOUR => 4.0,
+            age if age < DAY => 2.0,
+            age if age < WEEK => 0.5,
+            _ => 0.25,
+        };
+        self.rank * factor
+    }
+}
+
+/// Visited directories ranked by frequency and recency, kept in memory and
+/// saved to `path` on exit.
+#[derive(Debug, Default)]
+pub struct Frecency {
+    path: Option<PathBuf>,
+    dirs: IndexMap<PathBuf, Visits>,
+    /// Visits recorded since the last save
+    dirty: bool,
+}
+
+impl Frecency {
+    /// A missing file is an empty database.
+    pub fn load(path: PathBuf) -> io::Result<Self> {
+        let dirs = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), e.message()),
+                )
+            })?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => IndexMap::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Frecency {
+            path: Some(path),
+            dirs,
+            dirty: false,
+        })
+    }
+
+    pub fn get(&self, dir: &Path) -> Option<&Visits> {
+        self.dirs.get(dir)
+    }
+
+    pub fn record(&mut self, dir: &Path, now: u64) {
+        let visits = self.dirs.entry(dir.to_path_buf()).or_insert(Visits {
+            rank: 0.0,
+            last_visit: now,
+        });
+        visits.rank += 1.0;
+        visits.last_visit = now;
+        self.age();
+        self.dirty = true;
+    }
+
+    /// Highest scoring existing directory matching `keywords`, other than `current`.
+    pub fn best_match(&self, keywords: &[&str], current: &Path, now: u64) -> Option<PathBuf> {
+        self.dirs
+            .iter()
+            .filter(|(dir, _)| dir.as_path() != current && matches(dir, keywords))
+            .filter(|(dir, _)| dir.is_dir())
+            .max_by(|(_, a), (_, b)| a.score(now).total_cmp(&b.score(now)))
+            .map(|(dir, _)| dir.clone())
+    }
+
+    fn age(&mut self) {
+        let total: f64 = self.dirs.values().map(|v| v.rank).sum();
+        if total <= MAX_TOTAL_RANK {
+            return;
+        }
+        let factor = 0.9 * MAX_TOTAL_RANK / total;
+        for visits in self.dirs.values_mut() {
+            visits.rank *= factor;
+        }
+        self.dirs.retain(|_, visits| visits.rank >= 1.0);
+    }
+
+    /// Writes the database if anything was recorded since it was loaded or last saved.
+    pub fn save(&mut self) -> io::Result<()> {
+        let Some(path) = self.path.as_ref().filter(|_| self.dirty) else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = toml::to_string(&self.dirs)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, content)?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// Like zoxide: the keywords appear in the path in order, ignoring case, and
+/// the last one is in the final component.
+pub fn matches(dir: &Path, keywords: &[&str]) -> bool {
+    let path = dir.to_string_lossy().to_lowercase();
+    let mut rest = path.as_str();
+    for keyword in keywords {
+        let keyword = keyword.to_lowercase();
+        match rest.find(&keyword) {
+            Some(id) => rest = &rest[id + keyword.len()..],
+            None => return false,
+        }
+    }
+    let last_component = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    keywords
+        .last()
+        .is_none_or(|last| last_component.contains(&last.to_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keywords_match_in_order_ending_in_last_component() {
+        let dir = Path::new("/home/user/Work/web/api");
+        assert!(matches(dir, &["web", "api"]));
+        assert!(matches(dir, &["WORK", "a"]));
+        assert!(!matches(dir, &["api", "web"]));
+        assert!(!matches(dir, &["web"]));
+    }
+
+    #[test]
+    fn ranks_by_visits_and_recency() {
+        let root = tempfile::tempdir().unwrap();
+        let old = root.path().join("old/api");
+        let recent = root.path().join("recent/api");
+        fs::create_dir_all(&old).unwrap();
+        fs::create_dir_all(&recent).unwrap();
+        let now = 100 * WEEK;
+
+        let mut db = Frecency::load(root.path().join("frecency.toml")).unwrap();
+        for _ in 0..3 {
+            db.record(&old, now - 2 * WEEK);
+        }
+        db.record(&recent, now);
+        assert_eq!(
+            db.best_match(&["api"], root.path(), now),
+            Some(recent.clone())
+        );
+        assert_eq!(db.best_match(&["api"], &recent, now), Some(old.clone()));
+        assert_eq!(db.best_match(&["nothing"], root.path(), now), None);
+        assert!(!root.path().join("frecency.toml").exists());
+        db.save().unwrap();
+
+        let db = Frecency::load(root.path().join("frecency.toml")).unwrap();
+        assert_eq!(db.get(&old).map(|v| v.rank), Some(3.0));
+    }
+
+    #[test]
+    fn aging_drops_rare_dirs() {
+        let mut db = Frecency::default();
+        db.dirs.insert(
+            PathBuf::from("/often"),
+            Visits {
+                rank: MAX_TOTAL_RANK,
+                last_visit: 0,
+            },
+        );
+        db.record(Path::new("/once"), 0);
+        assert!(db.get(Path::new("/once")).is_none());
+        assert!(db.get(Path::new("/often")).unwrap().rank < MAX_TOTAL_RANK);
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use crate::app::{
+    model::matcher::Matcher,
+    utils::fs::{is_binary_file, walker},
+};
+
+/// Hits taken from the searcher per poll, so a busy search cannot stall a frame.
+const POLL_BATCH: usize = 1000;
+/// The search stops here; a query this common is better narrowed down
+const MAX_HITS: usize = 10_000;
+/// Longer lines are cut, the rest of them would be off screen anyway
+const MAX_TEXT_CHARS: usize = 256;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrepHit {
+    /// Relative to the search root
+    pub path: PathBuf,
+    /// 1-based
+    pub line: usize,
+    pub text: String,
+}
+
+impl GrepHit {
+    /// `path:line:text`, as grep prints it.
+    pub fn label(&self) -> String {
+        format!("{}:{}:{}", self.path.display(), self.line, self.text)
+    }
+}
+
+/// Lines matching a query in the text files under `root`, streamed in from a
+/// searcher thread.
+#[derive(Debug)]
+pub struct Grep {
+    pub root: PathBuf,
+    pub hits: Vec<GrepHit>,
+    pub selected: usize,
+    /// Set when the search stopped at `MAX_HITS`
+    pub truncated: bool,
+    query: String,
+    /// Dropped once the search is over; dropping it early stops the searcher
+    receiver: Option<Receiver<GrepHit>>,
+}
+
+impl Grep {
+    /// Starts searching `root`, skipping binary files, hidden entries unless
+    /// `show_hidden` and ignored ones when `respect_ignore`.
+    pub fn spawn(root: &Path, matcher: Matcher, show_hidden: bool, respect_ignore: bool) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let walker = walker(root, show_hidden, respect_ignore).build();
+        let walk_root = root.to_path_buf();
+        let query = matcher.query().to_string();
+
+        thread::spawn(move || {
+            let mut found = 0;
+            for entry in walker.flatten() {
+                if !entry.file_type().is_some_and(|t| t.is_file())
+                    || is_binary_file(entry.path()).unwrap_or(true)
+                {
+                    continue;
+                }
+                let Ok(file) = File::open(entry.path()) else {
+                    continue;
+                };
+                let path = entry
+                    .path()
+                    .strip_prefix(&walk_root)
+                    .unwrap_or(entry.path())
+                    .to_path_buf();
+                // invalid UTF-8 is replaced so the rest of the file is still searched
+                for (id, line) in BufReader::new(file)
+                    .split(b'\n')
+                    .map_while(Result::ok)
+                    .enumerate()
+                {
+                    let text = String::from_utf8_lossy(&line);
+                    if !matcher.is_match(&text) {
+                        continue;
+                    }
+                    let hit = GrepHit {
+                        path: path.clone(),
+                        line: id + 1,
+                        text: text.trim().chars().take(MAX_TEXT_CHARS).collect(),
+                    };
+                    found += 1;
+                    // one past the limit tells the results they are cut short;
+                    // a failed send means they were closed
+                    if sender.send(hit).is_err() || found > MAX_HITS {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Grep {
+            root: root.to_path_buf(),
+            hits: vec![],
+            selected: 0,
+            truncated: false,
+            query,
+            receiver: Some(receiver),
+        }
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Takes the hits found since the last call. Returns whether anything changed.
+    pub fn poll(&mut self) -> bool {
+        let Some(receiver) = &self.receiver else {
+            return false;
+        };
+        let from = self.hits.len();
+        let mut finished = false;
+        while self.hits.len() - from < POLL_BATCH {
+            match receiver.try_recv() {
+                Ok(_) if self.hits.len() == MAX_HITS => self.truncated = true,
+                Ok(hit) => self.hits.push(hit),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+        if finished {
+            self.receiver = None;
+        }
+        finished || self.hits.len() > from
+    }
+
+    pub fn move_selection(&mut self, step: isize) {
+        if self.hits.is_empty() {
+            return;
+        }
+        self.selected = self
+            .selected
+            .saturating_add_signed(step)
+            .min(self.hits.len() - 1);
+    }
+
+    pub fn selected_hit(&self) -> Option<&GrepHit> {
+        self.hits.get(self.selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::app::model::matcher::MatchEngine;
+
+    #[test]
+    fn finds_lines_in_text_files() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir(root.path().join("src")).unwrap();
+        fs::write(
+            root.path().join("src/main.rs"),
+            "fn main() {\n    todo!()\n}\n",
+        )
+        .unwrap();
+        fs::write(root.path().join("notes.txt"), "TODO: more\n").unwrap();
+        fs::write(
+            root.path().join("caf\u{e9}.md"),
+            "\u{2500} r\u{e9}sum\u{e9}: todo\n",
+        )
+        .unwrap();
+        fs::write(root.path().join("blob.bin"), b"todo\0\x01").unwrap();
+
+        let matcher = Matcher::new("todo", MatchEngine::Substring).unwrap();
+        let mut grep = Grep::spawn(root.path(), matcher, false, true);
+        while grep.is_searching() {
+            grep.poll();
+        }
+        let mut labels: Vec<String> = grep.hits.iter().map(GrepHit::label).collect();
+        labels.sort();
+        assert_eq!(
+            labels,
+            [
+                "caf\u{e9}.md:1:\u{2500} r\u{e9}sum\u{e9}: todo",
+                "notes.txt:1:TODO: more",
+                "src/main.rs:2:todo!()"
+            ]
+        );
+
+        grep.move_selection(5);
+        assert_eq!(grep.selected, 2);
+    }
+
+    #[test]
+    fn keeps_reading_past_invalid_utf8_and_flags_the_limit() {
+        let root = tempfile::tempdir().unwrap();
+        // past the sampled KiB, so the file still counts as text
+        let mut mixed = vec![b'x'; 2048];
+        mixed.extend(b"\n\xff\xfe\nlast todo\n");
+        fs::write(root.path().join("mixed.txt"), mixed).unwrap();
+        fs::write(root.path().join("many.txt"), "todo\n".repeat(MAX_HITS + 1)).unwrap();
+
+        let matcher = Matcher::new("todo", MatchEngine::Substring).unwrap();
+        let mut grep = Grep::spawn(root.path(), matcher, false, true);
+        while grep.is_searching() {
+            grep.poll();
+        }
+        assert!(grep.truncated);
+        assert_eq!(grep.hits.len(), MAX_HITS);
+
+        let matcher = Matcher::new("last", MatchEngine::Substring).unwrap();
+        let mut grep = Grep::spawn(root.path(), matcher, false, true);
+        while grep.is_searching() {
+            grep.poll();
+        }
+        assert!(!grep.truncated);
+        assert_eq!(grep.hits[0].label(), "mixed.txt:3:last todo");
+    }
+}
@@ -0,0 +1,109 @@
+use std::path::{Path, PathBuf};
+
+const MAX_JUMPS: usize = 100;
+
+/// Directories left through a non-linear move, oldest first.
+///
+/// `index` is the entry being visited; it equals `entries.len()` while the user
+/// is somewhere new, past the end of the list.
+#[derive(Debug, Default)]
+pub struct JumpList {
+    entries: Vec<PathBuf>,
+    index: usize,
+}
+
+impl JumpList {
+    pub fn entries(&self) -> &[PathBuf] {
+        &self.entries
+    }
+
+    /// Entry being visited, `None` when past the end of the list.
+    pub fn current(&self) -> Option<usize> {
+        (self.index < self.entries.len()).then_some(self.index)
+    }
+
+    /// Remembers `from` before jumping away from it; drops the forward history.
+    pub fn record(&mut self, from: &Path) {
+        self.entries.truncate(self.index);
+        self.entries.retain(|entry| entry != from);
+        self.entries.push(from.to_path_buf());
+        if self.entries.len() > MAX_JUMPS {
+            self.entries.remove(0);
+        }
+        self.index = self.entries.len();
+    }
+
+    pub fn back(&mut self, current: &Path, count: usize) -> Option<PathBuf> {
+        self.anchor(current);
+        let target = self.index.checked_sub(1)?;
+        self.select(current, target.saturating_sub(count.saturating_sub(1)))
+    }
+
+    pub fn forward(&mut self, count: usize) -> Option<PathBuf> {
+        let last = self.entries.len().checked_sub(1)?;
+        if self.index >= last {
+            return None;
+        }
+        self.index = (self.index + count).min(last);
+        Some(self.entries[self.index].clone())
+    }
+
+    pub fn select(&mut self, current: &Path, target: usize) -> Option<PathBuf> {
+        self.anchor(current);
+        if target >= self.entries.len() || target == self.index {
+            return None;
+        }
+        self.index = target;
+        Some(self.entries[target].clone())
+    }
+
+    /// Adds the directory being left at the end, so jumping forward can come back to it.
+    fn anchor(&mut self, current: &Path) {
+        if self.index < self.entries.len() {
+            return;
+        }
+        if self.entries.last().is_none_or(|last| last != current) {
+            self.entries.push(current.to_path_buf());
+        }
+        self.index = self.entries.len() - 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(p: &str) -> PathBuf {
+        PathBuf::from(p)
+    }
+
+    #[test]
+    fn back_and_forward() {
+        let mut jumps = JumpList::default();
+        jumps.record(Path::new("/a"));
+        jumps.record(Path::new("/b"));
+
+        assert_eq!(jumps.back(Path::new("/c/deep"), 1), Some(path("/b")));
+        assert_eq!(jumps.back(Path::new("/b"), 1), Some(path("/a")));
+        assert_eq!(jumps.back(Path::new("/a"), 1), None);
+        assert_eq!(jumps.forward(5), Some(path("/c/deep")));
+        assert_eq!(jumps.forward(1), None);
+        assert_eq!(jumps.current(), Some(2));
+    }
+
+    #[test]
+    fn record_drops_forward_history_and_duplicates() {
+        let mut jumps = JumpList::default();
+        jumps.record(Path::new("/a"));
+        jumps.record(Path::new("/b"));
+        jumps.back(Path::new("/c"), 2);
+
+        jumps.record(Path::new("/a"));
+        assert_eq!(jumps.entries(), [path("/a")]);
+        assert_eq!(jumps.current(), None);
+
+        jumps.record(Path::new("/d"));
+        jumps.record(Path::new("/a"));
+        assert_eq!(jumps.entries(), [path("/d"), path("/a")]);
+    }
+}
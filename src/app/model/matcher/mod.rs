@@ -0,0 +1,250 @@
+use std::ops::Range;
+
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use glob::{MatchOptions, Pattern};
+use regex_automata::{meta::Regex, util::syntax};
+use serde::{Deserialize, Serialize};
+
+use crate::app::utils::i18n::Lang;
+
+/// How a search query is matched against entry names.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchEngine {
+    /// Anywhere in the name
+    #[default]
+    Substring,
+    Prefix,
+    /// Whole name, `*.rs`
+    Glob,
+    Regex,
+    /// Characters in order, best scoring first
+    Fuzzy,
+}
+
+impl MatchEngine {
+    pub const ALL: [MatchEngine; 5] = [
+        MatchEngine::Substring,
+        MatchEngine::Prefix,
+        MatchEngine::Glob,
+        MatchEngine::Regex,
+        MatchEngine::Fuzzy,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MatchEngine::Substring => "substring",
+            MatchEngine::Prefix => "prefix",
+            MatchEngine::Glob => "glob",
+            MatchEngine::Regex => "regex",
+            MatchEngine::Fuzzy => "fuzzy",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|engine| engine.name() == name)
+    }
+
+    /// Whether matches are visited by score rather than in listing order.
+    pub fn is_ranked(self) -> bool {
+        self == MatchEngine::Fuzzy
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NameMatch {
+    /// Only fuzzy matches are scored, the rest are all 0
+    pub score: i64,
+    /// Byte ranges of the name to highlight
+    pub ranges: Vec<Range<usize>>,
+}
+
+#[derive(Debug, Clone)]
+enum Compiled {
+    Regex(Regex),
+    Glob(Pattern),
+    Fuzzy,
+}
+
+/// A compiled search query. Smart-case: it ignores case unless the query
+/// has an uppercase letter.
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    query: String,
+    engine: MatchEngine,
+    ignore_case: bool,
+    compiled: Compiled,
+}
+
+/// Same query with the same engine compiles to the same matcher.
+impl PartialEq for Matcher {
+    fn eq(&self, other: &Self) -> bool {
+        self.query == other.query && self.engine == other.engine
+    }
+}
+
+impl Matcher {
+    pub fn new(query: &str, engine: MatchEngine) -> Result<Self, String> {
+        let ignore_case = !has_uppercase(query, engine);
+        let invalid =
+            |e: &dyn std::fmt::Display| Lang::en_fmt("invalid_pattern", &[query, &e.to_string()]);
+        let regex = |pattern: &str| {
+            Regex::builder()
+                .syntax(syntax::Config::new().case_insensitive(ignore_case))
+                .build(pattern)
+                .map(Compiled::Regex)
+                .map_err(|e| invalid(&e))
+        };
+
+        let compiled = match engine {
+            MatchEngine::Substring => regex(&regex_syntax::escape(query))?,
+            MatchEngine::Prefix => regex(&format!("^{}", regex_syntax::escape(query)))?,
+            MatchEngine::Regex => regex(query)?,
+            MatchEngine::Glob => Compiled::Glob(Pattern::new(query).map_err(|e| invalid(&e))?),
+            MatchEngine::Fuzzy => Compiled::Fuzzy,
+        };
+        Ok(Matcher {
+            query: query.to_string(),
+            engine,
+            ignore_case,
+            compiled,
+        })
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn engine(&self) -> MatchEngine {
+        self.engine
+    }
+
+    pub fn is_match(&self, name: &str) -> bool {
+        self.find(name).is_some()
+    }
+
+    /// `None` when the name does not match; an empty query matches nothing.
+    pub fn find(&self, name: &str) -> Option<NameMatch> {
+        if self.query.is_empty() {
+            return None;
+        }
+        match &self.compiled {
+            Compiled::Regex(regex) => {
+                let ranges: Vec<Range<usize>> = regex
+                    .find_iter(name)
+                    .map(|m| m.range())
+                    .filter(|range| !range.is_empty())
+                    .collect();
+                // an empty regex match still counts, there is just nothing to highlight
+                (!ranges.is_empty() || regex.is_match(name))
+                    .then_some(NameMatch { score: 0, ranges })
+            }
+            Compiled::Glob(pattern) => {
+                let options = MatchOptions {
+                    case_sensitive: !self.ignore_case,
+                    require_literal_separator: false,
+                    require_literal_leading_dot: false,
+                };
+                pattern.matches_with(name, options).then(|| NameMatch {
+                    score: 0,
+                    // the pattern covers the whole name
+                    ranges: std::iter::once(0..name.len()).collect(),
+                })
+            }
+            Compiled::Fuzzy => {
+                let matcher = if self.ignore_case {
+                    SkimMatcherV2::default().ignore_case()
+                } else {
+                    SkimMatcherV2::default().respect_case()
+                };
+                let (score, indices) = matcher.fuzzy_indices(name, &self.query)?;
+                let ranges = name
+                    .char_indices()
+                    .enumerate()
+                    .filter(|(char_id, _)| indices.contains(char_id))
+                    .map(|(_, (byte_id, c))| byte_id..byte_id + c.len_utf8())
+                    .collect();
+                Some(NameMatch { score, ranges })
+            }
+        }
+    }
+}
+
+/// In a regex, the letter of an escape such as `\S` or `\W` is not the query's case.
+fn has_uppercase(query: &str, engine: MatchEngine) -> bool {
+    if engine != MatchEngine::Regex {
+        return query.chars().any(char::is_uppercase);
+    }
+    let mut escaped = false;
+    query.chars().any(|c| {
+        let upper = !escaped && c.is_uppercase();
+        escaped = !escaped && c == '\\';
+        upper
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(engine: MatchEngine, query: &str, name: &str) -> Option<Vec<(usize, usize)>> {
+        Matcher::new(query, engine)
+            .unwrap()
+            .find(name)
+            .map(|m| m.ranges.iter().map(|r| (r.start, r.end)).collect())
+    }
+
+    #[test]
+    fn engines_match_and_highlight() {
+        use MatchEngine::*;
+
+        assert_eq!(
+            ranges(Substring, "test", "unit_test.rs"),
+            Some(vec![(5, 9)])
+        );
+        assert_eq!(ranges(Prefix, "test", "unit_test.rs"), None);
+        assert_eq!(ranges(Prefix, "unit", "unit_test.rs"), Some(vec![(0, 4)]));
+        assert_eq!(ranges(Regex, r"\.rs$", "unit_test.rs"), Some(vec![(9, 12)]));
+        assert_eq!(ranges(Regex, r"\.rs$", "notes.rst"), None);
+        assert_eq!(ranges(Glob, "*.rs", "main.rs"), Some(vec![(0, 7)]));
+        assert_eq!(ranges(Glob, "*.rs", "main.rsx"), None);
+        assert_eq!(
+            ranges(Fuzzy, "mrs", "main.rs"),
+            Some(vec![(0, 1), (5, 6), (6, 7)])
+        );
+        // special characters are literal outside the regex engine
+        assert_eq!(ranges(Substring, "a.b", "axb"), None);
+        assert!(Matcher::new("(", Regex).is_err());
+    }
+
+    #[test]
+    fn smart_case() {
+        use MatchEngine::*;
+
+        for engine in MatchEngine::ALL {
+            let query = if engine == Glob { "*READ*" } else { "READ" };
+            assert!(Matcher::new(query, engine).unwrap().is_match("README"));
+            assert!(!Matcher::new(query, engine).unwrap().is_match("readme"));
+            let query = query.to_lowercase();
+            assert!(Matcher::new(&query, engine).unwrap().is_match("README"));
+        }
+
+        // escapes are not uppercase letters of the query
+        assert!(Matcher::new(r"read\S*", Regex)
+            .unwrap()
+            .is_match("README.md"));
+        assert!(Matcher::new(r"\Bme\W", Regex)
+            .unwrap()
+            .is_match("READ_ME.md"));
+        assert!(!Matcher::new(r"\\R", Regex).unwrap().is_match("\\r"));
+    }
+
+    #[test]
+    fn fuzzy_ranks_tighter_matches_higher() {
+        let matcher = Matcher::new("cfg", MatchEngine::Fuzzy).unwrap();
+        let tight = matcher.find("cfg.toml").unwrap().score;
+        let loose = matcher.find("crate_forge.rs").unwrap().score;
+        assert!(tight > loose);
+        assert_eq!(MatchEngine::from_name("fuzzy"), Some(MatchEngine::Fuzzy));
+    }
+}
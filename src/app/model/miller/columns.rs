@@ -1,11 +1,16 @@
+use std::fs::Metadata;
 use std::io::{self};
 use std::path::Path;
 
 use crate::app::config::constants::model::NUM_COLUMNS;
 use crate::app::model::file::{
-    calculate_file_size, count_dir_entries, get_file_permissions, get_last_modified,
+    build_full_path, calculate_file_size, count_dir_entries, get_file_kind, get_file_permissions,
+    get_last_modified,
 };
+use crate::app::model::matcher::Matcher;
 use crate::app::model::miller::entries::{DirEntry, FileEntry, FileVariant};
+use crate::app::model::miller::sort::{sort_entries, SortKey};
+use crate::app::model::miller::view::{DirViews, ViewOptions};
 
 #[derive(Debug)]
 pub struct MillerColumns {
@@ -17,41 +22,109 @@ impl MillerColumns {
     pub fn build_columns(
         current_dir: &Path,
         position_id: usize,
-        search_pattern: Option<String>,
-        show_hidden_files: bool,
+        search_pattern: Option<&Matcher>,
+        views: &DirViews,
+        global_view: &ViewOptions,
     ) -> io::Result<Self> {
         let selected_dir_entry = DirEntry {
             dir_name: Some(current_dir.to_path_buf()),
             with_meta: true,
         };
-        let selected_dir_files =
-            Self::parse_dir_files(&selected_dir_entry, &search_pattern, show_hidden_files)?;
+        let selected_dir_files = Self::parse_dir_files(
+            &selected_dir_entry,
+            search_pattern,
+            &Self::view_of(&selected_dir_entry, views, global_view),
+        )?;
 
+        Self::around(
+            current_dir,
+            selected_dir_entry,
+            selected_dir_files,
+            position_id,
+            search_pattern,
+            views,
+            global_view,
+        )
+    }
+
+    /// Columns with `listing` in place of the entries of `current_dir`, which
+    /// may come from anywhere below it.
+    pub fn build_listing(
+        current_dir: &Path,
+        listing: &[FileEntry],
+        position_id: usize,
+        search_pattern: Option<&Matcher>,
+        views: &DirViews,
+        global_view: &ViewOptions,
+    ) -> io::Result<Self> {
+        let selected_dir_entry = DirEntry {
+            dir_name: Some(current_dir.to_path_buf()),
+            with_meta: true,
+        };
+        let selected_dir_files = listing
+            .iter()
+            .cloned()
+            .map(|mut entry| {
+                let matched = search_pattern.is_some_and(|matcher| matcher.is_match(&entry.name));
+                match &mut entry.variant {
+                    FileVariant::Directory { is_matched, .. }
+                    | FileVariant::File { is_matched, .. } => *is_matched = matched,
+                }
+                entry
+            })
+            .collect();
+
+        Self::around(
+            current_dir,
+            selected_dir_entry,
+            selected_dir_files,
+            position_id,
+            search_pattern,
+            views,
+            global_view,
+        )
+    }
+
+    /// Adds the parent column and the column of the selected directory.
+    fn around(
+        current_dir: &Path,
+        selected_dir_entry: DirEntry,
+        selected_dir_files: Vec<FileEntry>,
+        position_id: usize,
+        search_pattern: Option<&Matcher>,
+        views: &DirViews,
+        global_view: &ViewOptions,
+    ) -> io::Result<Self> {
         let parent_dir_entry = DirEntry {
             dir_name: current_dir.parent().map(|e| e.to_path_buf()),
             with_meta: false,
         };
 
-        let parent_dir_files =
-            Self::parse_dir_files(&parent_dir_entry, &search_pattern, show_hidden_files)?;
-
-        let (child_dir_entry, child_dir_files) = if let Some(first_entry) =
-            selected_dir_files.get(position_id)
-        {
-            if matches!(first_entry.variant, FileVariant::Directory { .. }) {
-                let child_dir_entry = DirEntry {
-                    dir_name: Some(current_dir.join(&first_entry.name)),
-                    with_meta: true,
-                };
-                let child_files =
-                    Self::parse_dir_files(&child_dir_entry, &search_pattern, show_hidden_files)?;
-                (child_dir_entry, child_files)
+        let parent_dir_files = Self::parse_dir_files(
+            &parent_dir_entry,
+            search_pattern,
+            &Self::view_of(&parent_dir_entry, views, global_view),
+        )?;
+
+        let (child_dir_entry, child_dir_files) =
+            if let Some(first_entry) = selected_dir_files.get(position_id) {
+                if matches!(first_entry.variant, FileVariant::Directory { .. }) {
+                    let child_dir_entry = DirEntry {
+                        dir_name: Some(build_full_path(current_dir, first_entry)),
+                        with_meta: true,
+                    };
+                    let child_files = Self::parse_dir_files(
+                        &child_dir_entry,
+                        search_pattern,
+                        &Self::view_of(&child_dir_entry, views, global_view),
+                    )?;
+                    (child_dir_entry, child_files)
+                } else {
+                    (DirEntry::empty_dir(), vec![])
+                }
             } else {
                 (DirEntry::empty_dir(), vec![])
-            }
-        } else {
-            (DirEntry::empty_dir(), vec![])
-        };
+            };
 
         Ok(Self {
             files: [parent_dir_files, selected_dir_files, child_dir_files],
@@ -59,71 +132,109 @@ impl MillerColumns {
         })
     }
 
+    /// Every column follows the overrides of its own directory.
+    fn view_of(dir: &DirEntry, views: &DirViews, global_view: &ViewOptions) -> ViewOptions {
+        dir.dir_name
+            .as_deref()
+            .map(|dir| views.resolve(dir, global_view))
+            .unwrap_or_else(|| global_view.clone())
+    }
+
+    /// Entries of a single directory, as listed in the current column.
+    pub fn list_dir(
+        dir: &Path,
+        views: &DirViews,
+        global_view: &ViewOptions,
+    ) -> io::Result<Vec<FileEntry>> {
+        let dir_entry = DirEntry {
+            dir_name: Some(dir.to_path_buf()),
+            with_meta: true,
+        };
+        Self::parse_dir_files(&dir_entry, None, &views.resolve(dir, global_view))
+    }
+
     fn parse_dir_files(
         dir_entry: &DirEntry,
-        search_pattern: &Option<String>,
-        show_hidden_files: bool,
+        search_pattern: Option<&Matcher>,
+        view: &ViewOptions,
     ) -> io::Result<Vec<FileEntry>> {
         match &dir_entry.dir_name {
             Some(dir) => {
-                let mut entries: Vec<FileEntry> = std::fs::read_dir(dir)?
+                let mut entries: Vec<(FileEntry, SortKey)> = std::fs::read_dir(dir)?
                     .filter_map(|entry| {
                         let e = entry.ok()?;
                         let metadata = e.metadata().ok()?;
-                        let permissions =
-                            dir_entry.with_meta.then(|| get_file_permissions(&metadata));
-                        let last_modified = dir_entry
-                            .with_meta
-                            .then(|| get_last_modified(&metadata).unwrap_or(String::from("")));
                         let name = e.file_name().to_string_lossy().into_owned();
 
-                        let is_matched = search_pattern
-                            .as_ref()
-                            .is_some_and(|pattern| name.to_lowercase().starts_with(pattern));
+                        let is_matched =
+                            search_pattern.is_some_and(|matcher| matcher.is_match(&name));
 
-                        if !show_hidden_files && name.starts_with('.') {
+                        if !view.is_visible(&name) {
                             return None;
                         }
 
-                        let variant = if metadata.is_dir() {
-                            let len = dir_entry.with_meta.then(|| count_dir_entries(e.path()));
-                            FileVariant::Directory {
-                                len,
-                                permissions,
-                                last_modified,
-                                is_matched,
-                            }
-                        } else {
-                            let size = dir_entry.with_meta.then(|| calculate_file_size(metadata));
-                            FileVariant::File {
-                                size,
-                                permissions,
-                                last_modified,
-                                is_matched,
-                            }
+                        let sort_key = SortKey {
+                            modified: metadata.modified().ok(),
+                            size: metadata.len(),
                         };
+                        let entry = Self::entry_from_metadata(
+                            name,
+                            &e.path(),
+                            metadata,
+                            dir_entry.with_meta,
+                            is_matched,
+                        );
 
-                        Some(FileEntry { name, variant })
+                        Some((entry, sort_key))
                     })
                     .collect();
 
-                entries.sort_by(|a, b| {
-                    match (
-                        matches!(a.variant, FileVariant::Directory { .. }),
-                        matches!(b.variant, FileVariant::Directory { .. }),
-                    ) {
-                        (true, false) => std::cmp::Ordering::Less,
-                        (false, true) => std::cmp::Ordering::Greater,
-                        _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-                    }
-                });
-
-                Ok(entries)
+                sort_entries(&mut entries, view.sort);
+
+                Ok(entries.into_iter().map(|(entry, _)| entry).collect())
             }
             None => Ok(vec![]),
         }
     }
 
+    /// An entry named `name` for the file at `path`, whose `metadata` must not
+    /// follow symlinks.
+    pub fn entry_from_metadata(
+        name: String,
+        path: &Path,
+        metadata: Metadata,
+        with_meta: bool,
+        is_matched: bool,
+    ) -> FileEntry {
+        let permissions = with_meta.then(|| get_file_permissions(&metadata));
+        let last_modified =
+            with_meta.then(|| get_last_modified(&metadata).unwrap_or(String::from("")));
+        let kind = get_file_kind(path, &metadata);
+        let variant = if metadata.is_dir() {
+            let len = with_meta.then(|| count_dir_entries(path));
+            FileVariant::Directory {
+                len,
+                permissions,
+                last_modified,
+                is_matched,
+            }
+        } else {
+            let size = with_meta.then(|| calculate_file_size(metadata));
+            FileVariant::File {
+                size,
+                permissions,
+                last_modified,
+                is_matched,
+            }
+        };
+        FileEntry {
+            name,
+            variant,
+            kind,
+            path: None,
+        }
+    }
+
     pub fn check_is_current_dir_is_not_empty(files: &[FileEntry]) -> bool {
         !files.is_empty()
     }
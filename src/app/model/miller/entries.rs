@@ -16,10 +16,29 @@ pub enum FileVariant {
     },
 }
 
+/// What the entry is on disk, used for `LS_COLORS` file-type codes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum FileKind {
+    #[default]
+    Regular,
+    Directory,
+    Executable,
+    Symlink,
+    /// Symlink whose target does not exist
+    Orphan,
+    Pipe,
+    Socket,
+    BlockDevice,
+    CharDevice,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct FileEntry {
     pub name: String,
     pub variant: FileVariant,
+    pub kind: FileKind,
+    /// Set when the entry does not live in the listed directory, as in find results
+    pub path: Option<PathBuf>,
 }
 
 #[derive(Debug, PartialEq)]
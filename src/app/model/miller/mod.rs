@@ -1,3 +1,5 @@
 pub mod columns;
 pub mod entries;
 pub mod positions;
+pub mod sort;
+pub mod view;
@@ -0,0 +1,171 @@
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::model::miller::entries::{FileEntry, FileVariant};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    #[default]
+    Name,
+    Mtime,
+    Size,
+    Extension,
+}
+
+impl SortBy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "name" => Some(SortBy::Name),
+            "mtime" => Some(SortBy::Mtime),
+            "size" => Some(SortBy::Size),
+            "ext" | "extension" => Some(SortBy::Extension),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Sort {
+    pub by: SortBy,
+    pub reverse: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct SortKey {
+    pub modified: Option<SystemTime>,
+    pub size: u64,
+}
+
+pub fn sort_entries(entries: &mut [(FileEntry, SortKey)], sort: Sort) {
+    entries.sort_by(|(a, a_key), (b, b_key)| {
+        let a_is_dir = matches!(a.variant, FileVariant::Directory { .. });
+        let b_is_dir = matches!(b.variant, FileVariant::Directory { .. });
+        if a_is_dir != b_is_dir {
+            // directories always stay on top
+            return b_is_dir.cmp(&a_is_dir);
+        }
+
+        let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        let ordering = match sort.by {
+            SortBy::Name => by_name(),
+            SortBy::Mtime => b_key.modified.cmp(&a_key.modified).then_with(by_name),
+            SortBy::Size => b_key.size.cmp(&a_key.size).then_with(by_name),
+            SortBy::Extension => extension(&a.name)
+                .cmp(&extension(&b.name))
+                .then_with(by_name),
+        };
+
+        if sort.reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+fn extension(name: &str) -> String {
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => ext.to_lowercase(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::app::model::miller::entries::FileKind;
+
+    use super::*;
+
+    fn entry(name: &str, is_dir: bool, size: u64, age: u64) -> (FileEntry, SortKey) {
+        let variant = if is_dir {
+            FileVariant::Directory {
+                len: None,
+                permissions: None,
+                last_modified: None,
+                is_matched: false,
+            }
+        } else {
+            FileVariant::File {
+                size: None,
+                permissions: None,
+                last_modified: None,
+                is_matched: false,
+            }
+        };
+        (
+            FileEntry {
+                name: name.into(),
+                variant,
+                kind: FileKind::default(),
+                path: None,
+            },
+            SortKey {
+                modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1000 - age)),
+                size,
+            },
+        )
+    }
+
+    fn names(entries: &[(FileEntry, SortKey)]) -> Vec<&str> {
+        entries.iter().map(|(e, _)| e.name.as_str()).collect()
+    }
+
+    fn sample() -> Vec<(FileEntry, SortKey)> {
+        vec![
+            entry("b.txt", false, 10, 30),
+            entry("a.rs", false, 30, 20),
+            entry("src", true, 0, 50),
+            entry("C.md", false, 20, 10),
+        ]
+    }
+
+    #[test]
+    fn sort_by_name_keeps_dirs_first() {
+        let mut entries = sample();
+        sort_entries(&mut entries, Sort::default());
+        assert_eq!(names(&entries), vec!["src", "a.rs", "b.txt", "C.md"]);
+    }
+
+    #[test]
+    fn sort_by_mtime_newest_first() {
+        let mut entries = sample();
+        sort_entries(
+            &mut entries,
+            Sort {
+                by: SortBy::Mtime,
+                reverse: false,
+            },
+        );
+        assert_eq!(names(&entries), vec!["src", "C.md", "a.rs", "b.txt"]);
+    }
+
+    #[test]
+    fn sort_by_size_reversed() {
+        let mut entries = sample();
+        sort_entries(
+            &mut entries,
+            Sort {
+                by: SortBy::Size,
+                reverse: true,
+            },
+        );
+        assert_eq!(names(&entries), vec!["src", "b.txt", "C.md", "a.rs"]);
+    }
+
+    #[test]
+    fn sort_by_extension() {
+        let mut entries = sample();
+        sort_entries(
+            &mut entries,
+            Sort {
+                by: SortBy::Extension,
+                reverse: false,
+            },
+        );
+        assert_eq!(names(&entries), vec!["src", "C.md", "a.rs", "b.txt"]);
+    }
+}
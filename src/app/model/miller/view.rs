@@ -0,0 +1,224 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::app::model::{
+    matcher::Matcher,
+    miller::sort::{Sort, SortBy},
+};
+
+/// Overrides stored for one directory; unset fields follow the global view.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct DirView {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<SortBy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse: Option<bool>,
+    /// Case-insensitive substring the entry names must contain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview: Option<bool>,
+}
+
+impl DirView {
+    pub fn is_empty(&self) -> bool {
+        *self == DirView::default()
+    }
+}
+
+/// Effective view of a directory after applying its overrides.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewOptions {
+    pub show_hidden: bool,
+    pub sort: Sort,
+    pub filter: Option<String>,
+    /// Set with `f` for this session; hides the names it does not match
+    pub session_filter: Option<Matcher>,
+    pub preview: bool,
+}
+
+impl Default for ViewOptions {
+    fn default() -> Self {
+        ViewOptions {
+            show_hidden: false,
+            sort: Sort::default(),
+            filter: None,
+            session_filter: None,
+            preview: true,
+        }
+    }
+}
+
+impl ViewOptions {
+    pub fn is_visible(&self, name: &str) -> bool {
+        if !self.show_hidden && name.starts_with('.') {
+            return false;
+        }
+        self.filter
+            .as_ref()
+            .is_none_or(|filter| name.to_lowercase().contains(&filter.to_lowercase()))
+            && self
+                .session_filter
+                .as_ref()
+                .is_none_or(|matcher| matcher.is_match(name))
+    }
+}
+
+/// Per-directory overrides, persisted as a TOML table keyed by directory path.
+#[derive(Debug, Default, PartialEq)]
+pub struct DirViews {
+    /// State file, `None` keeps the overrides in memory only
+    path: Option<PathBuf>,
+    dirs: IndexMap<PathBuf, DirView>,
+    /// Filters that last until cleared or quit, never written to the state file
+    session_filters: HashMap<PathBuf, Matcher>,
+}
+
+impl DirViews {
+    /// A missing file is not an error; an unreadable one is, so it does not get overwritten.
+    pub fn load(path: PathBuf) -> io::Result<Self> {
+        let dirs = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), e.message()),
+                )
+            })?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => IndexMap::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(DirViews {
+            path: Some(path),
+            dirs,
+            session_filters: HashMap::new(),
+        })
+    }
+
+    pub fn get(&self, dir: &Path) -> Option<&DirView> {
+        self.dirs.get(dir)
+    }
+
+    pub fn resolve(&self, dir: &Path, global: &ViewOptions) -> ViewOptions {
+        let session_filter = self.session_filter(dir).cloned();
+        let Some(view) = self.get(dir) else {
+            return ViewOptions {
+                session_filter,
+                ..global.clone()
+            };
+        };
+        ViewOptions {
+            show_hidden: view.hidden.unwrap_or(global.show_hidden),
+            sort: Sort {
+                by: view.sort.unwrap_or(global.sort.by),
+                reverse: view.reverse.unwrap_or(global.sort.reverse),
+            },
+            filter: view.filter.clone().or_else(|| global.filter.clone()),
+            session_filter,
+            preview: view.preview.unwrap_or(global.preview),
+        }
+    }
+
+    pub fn session_filter(&self, dir: &Path) -> Option<&Matcher> {
+        self.session_filters.get(dir)
+    }
+
+    /// `None` clears the filter of `dir`.
+    pub fn set_session_filter(&mut self, dir: &Path, filter: Option<Matcher>) {
+        match filter {
+            Some(matcher) => self.session_filters.insert(dir.to_path_buf(), matcher),
+            None => self.session_filters.remove(dir),
+        };
+    }
+
+    /// Applies `update` to the overrides of `dir` and writes the state file.
+    pub fn update(&mut self, dir: &Path, update: impl FnOnce(&mut DirView)) -> io::Result<()> {
+        let view = self.dirs.entry(dir.to_path_buf()).or_default();
+        update(view);
+        if view.is_empty() {
+            self.dirs.shift_remove(dir);
+        }
+        self.save()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = toml::to_string(&self.dirs)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_overrides_per_dir() {
+        let mut views = DirViews::default();
+        views
+            .update(Path::new("/dotfiles"), |view| {
+                view.hidden = Some(true);
+                view.sort = Some(SortBy::Mtime);
+            })
+            .unwrap();
+
+        let global = ViewOptions::default();
+        let dotfiles = views.resolve(Path::new("/dotfiles"), &global);
+        assert!(dotfiles.show_hidden);
+        assert_eq!(dotfiles.sort.by, SortBy::Mtime);
+        assert!(dotfiles.preview);
+        assert_eq!(views.resolve(Path::new("/other"), &global), global);
+
+        views
+            .update(Path::new("/dotfiles"), |view| *view = DirView::default())
+            .unwrap();
+        assert!(views.get(Path::new("/dotfiles")).is_none());
+    }
+
+    #[test]
+    fn filter_and_hidden_visibility() {
+        let options = ViewOptions {
+            filter: Some("RC".into()),
+            ..ViewOptions::default()
+        };
+        assert!(options.is_visible("zshrc"));
+        assert!(!options.is_visible(".bashrc"));
+        assert!(!options.is_visible("notes"));
+    }
+
+    #[test]
+    fn round_trips_state_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join("views.toml");
+
+        let mut views = DirViews::load(path.clone()).unwrap();
+        views
+            .update(Path::new("/home/user/dotfiles"), |view| {
+                view.hidden = Some(true);
+                view.sort = Some(SortBy::Mtime);
+                view.reverse = Some(true);
+                view.preview = Some(false);
+            })
+            .unwrap();
+
+        let loaded = DirViews::load(path.clone()).unwrap();
+        assert_eq!(loaded, views);
+
+        fs::write(&path, "[\"/x\"]\nbogus = 1\n").unwrap();
+        assert!(DirViews::load(path).is_err());
+    }
+}
@@ -1,4 +1,13 @@
+pub mod chooser;
 pub mod clipboard;
+pub mod dual_pane;
 pub mod file;
+pub mod find;
+pub mod finder;
+pub mod frecency;
+pub mod grep;
+pub mod jumps;
+pub mod matcher;
 pub mod miller;
 pub mod notification;
+pub mod tabs;
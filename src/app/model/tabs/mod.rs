@@ -0,0 +1,113 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::app::model::{jumps::JumpList, matcher::Matcher, miller::entries::FileEntry};
+
+/// Navigation context of one tab. The tab on screen keeps these fields in `State`.
+#[derive(Debug, Default)]
+pub struct Tab {
+    pub current_dir: PathBuf,
+    pub positions_map: HashMap<PathBuf, usize>,
+    pub marked: Vec<FileEntry>,
+    pub search_pattern: Option<Matcher>,
+    pub jumps: JumpList,
+}
+
+#[derive(Debug)]
+pub struct TabList {
+    /// Tabs in bar order; the one at `active` is stale until it is stored again
+    pub list: Vec<Tab>,
+    pub active: usize,
+}
+
+impl TabList {
+    pub fn new(current_dir: &Path) -> Self {
+        TabList {
+            list: vec![Tab {
+                current_dir: current_dir.to_path_buf(),
+                ..Tab::default()
+            }],
+            active: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Index `step` tabs away from the active one, wrapping around.
+    pub fn offset(&self, step: isize) -> usize {
+        (self.active as isize + step).rem_euclid(self.len() as isize) as usize
+    }
+
+    /// Moves the active tab `step` places along the bar, stopping at either end.
+    pub fn move_active(&mut self, step: isize) {
+        let target = self
+            .active
+            .saturating_add_signed(step)
+            .min(self.len().saturating_sub(1));
+        let tab = self.list.remove(self.active);
+        self.list.insert(target, tab);
+        self.active = target;
+    }
+
+    /// Directory names for the tab bar; `current_dir` stands in for the active tab.
+    pub fn titles(&self, current_dir: &Path) -> Vec<String> {
+        self.list
+            .iter()
+            .enumerate()
+            .map(|(id, tab)| {
+                let dir = if id == self.active {
+                    current_dir
+                } else {
+                    &tab.current_dir
+                };
+                dir.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| dir.display().to_string())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tabs(dirs: &[&str]) -> TabList {
+        TabList {
+            list: dirs
+                .iter()
+                .map(|dir| Tab {
+                    current_dir: PathBuf::from(dir),
+                    ..Tab::default()
+                })
+                .collect(),
+            active: 0,
+        }
+    }
+
+    #[test]
+    fn offset_wraps() {
+        let tabs = tabs(&["/a", "/b", "/c"]);
+        assert_eq!(tabs.offset(1), 1);
+        assert_eq!(tabs.offset(-1), 2);
+        assert_eq!(tabs.offset(4), 1);
+    }
+
+    #[test]
+    fn move_active_keeps_it_active() {
+        let mut tabs = tabs(&["/a", "/b", "/c"]);
+        tabs.move_active(5);
+        assert_eq!(tabs.active, 2);
+        assert_eq!(tabs.titles(Path::new("/a")), ["b", "c", "a"]);
+        tabs.move_active(-1);
+        assert_eq!(tabs.titles(Path::new("/")), ["b", "/", "c"]);
+    }
+}
@@ -3,10 +3,9 @@ use std::io;
 use crate::app::{
     model::{
         file::{build_full_path, get_current_file},
-        miller::positions::get_position,
         notification::Notification,
     },
-    state::{FileManager, HintBar, Mode, State},
+    state::{FileManager, HintBar, Mode, Navigation, State},
     ui::modal::ModalKind,
     utils::{config_parser::save_config, i18n::Lang},
 };
@@ -18,6 +17,7 @@ pub trait Bookmarks {
     fn add_to_bookmarks(&mut self);
     fn commit_new_bookmark(&mut self, alias: String);
     fn delete_from_bookmarks(&mut self);
+    fn delete_bookmark(&mut self, alias: &str) -> bool;
     fn open_dir_from_bookmark(&mut self) -> io::Result<()>;
 }
 
@@ -91,15 +91,27 @@ impl<'a> Bookmarks for State<'a> {
         }
     }
 
+    fn delete_bookmark(&mut self, alias: &str) -> bool {
+        let deleted = self.config.bookmarks.shift_remove(alias).is_some();
+        if deleted {
+            let _ = save_config(&self.config);
+
+            self.notification = Notification::Info {
+                msg: Lang::en("bookmark_deleted").into(),
+            }
+            .into()
+        }
+        deleted
+    }
+
     fn open_dir_from_bookmark(&mut self) -> io::Result<()> {
         if let Mode::Bookmarks { position_id } = self.mode {
             if let Some((_, value)) = self.config.bookmarks.get_index(position_id) {
-                let millers_id = get_position(&self.positions_map, &self.current_dir);
                 match () {
                     _ if value.is_dir() => {
-                        self.current_dir = value.clone();
-                        let _ = self.reset_state(millers_id);
+                        let dir = value.clone();
                         self.mode = Mode::Normal;
+                        self.change_dir(dir)?;
                     }
                     _ if value.is_file() => {
                         self.execute_file(value.clone());
@@ -0,0 +1,115 @@
+use std::{io, path::Path};
+
+use crate::app::{
+    model::chooser::Chooser,
+    state::{Navigation, State},
+};
+
+pub trait Picker {
+    fn choose_file(&mut self, opened: &Path) -> bool;
+    fn confirm_choice(&mut self) -> io::Result<()>;
+}
+
+impl<'a> Picker for State<'a> {
+    /// In picker mode, takes `opened` (or the marked set for `--choosefiles`)
+    /// as the choice. Returns false when not picking files.
+    fn choose_file(&mut self, opened: &Path) -> bool {
+        let Some(picks_many) = self
+            .chooser
+            .as_ref()
+            .filter(|chooser| chooser.picks_files())
+            .map(Chooser::picks_many)
+        else {
+            return false;
+        };
+        let paths = if picks_many && !self.marked.is_empty() {
+            self.selected_paths()
+        } else {
+            vec![opened.to_path_buf()]
+        };
+        if let Some(chooser) = &mut self.chooser {
+            chooser.choose(paths);
+        }
+        true
+    }
+
+    /// Picks the marked set; without marks it opens the entry under the cursor.
+    fn confirm_choice(&mut self) -> io::Result<()> {
+        let picks_files = self.chooser.as_ref().is_some_and(Chooser::picks_files);
+        if !picks_files || self.marked.is_empty() {
+            return self.navigate_to_child_or_exec();
+        }
+        let paths = self.selected_paths();
+        if let Some(chooser) = &mut self.chooser {
+            chooser.choose(paths);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+    use crate::app::{
+        state::{FileManager, Mark},
+        test_utils::create_test_state,
+    };
+
+    fn picker(root: &Path, many: bool) -> Chooser {
+        let output = Some(root.join("chosen"));
+        if many {
+            Chooser::new(None, output, None, false).unwrap()
+        } else {
+            Chooser::new(output, None, None, false).unwrap()
+        }
+    }
+
+    #[test]
+    fn opening_a_file_picks_it() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir(root.join("dir")).unwrap();
+        fs::write(root.join("dir/a.txt"), "").unwrap();
+
+        let mut state = create_test_state();
+        state.chooser = Some(picker(&root, false));
+        state.change_dir(root.clone()).unwrap();
+
+        // a directory is entered, not picked
+        state.navigate_to_child_or_exec().unwrap();
+        assert!(!state.chooser.as_ref().unwrap().is_done());
+        state.execute_file(root.join("dir/a.txt"));
+        let chooser = state.chooser.as_ref().unwrap();
+        assert!(chooser.is_done());
+        chooser.write(&state.current_dir).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("chosen")).unwrap(),
+            format!("{}\n", root.join("dir/a.txt").display())
+        );
+    }
+
+    #[test]
+    fn confirm_picks_marked_set() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::write(root.join("a"), "").unwrap();
+        fs::write(root.join("b"), "").unwrap();
+
+        let mut state = create_test_state();
+        state.chooser = Some(picker(&root, true));
+        state.change_dir(root.clone()).unwrap();
+        state.mark_next(2);
+        state.confirm_choice().unwrap();
+
+        let chooser = state.chooser.as_ref().unwrap();
+        chooser.write(&state.current_dir).unwrap();
+        let chosen: Vec<PathBuf> = fs::read_to_string(root.join("chosen"))
+            .unwrap()
+            .lines()
+            .map(PathBuf::from)
+            .collect();
+        assert_eq!(chosen, [root.join("a"), root.join("b")]);
+    }
+}
@@ -0,0 +1,631 @@
+use std::path::PathBuf;
+
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::app::{
+    model::{
+        matcher::MatchEngine,
+        miller::{
+            positions::get_position,
+            sort::{Sort, SortBy},
+            view::DirView,
+        },
+        notification::Notification,
+    },
+    state::{
+        Bookmarks, ConfigReload, FileManager, Find, FrecencyJump, LocalView, Mode, Navigation,
+        State, Tabs,
+    },
+    ui::modal::{BottomLineModalAction, ModalKind},
+    utils::{
+        common_prefix,
+        config_parser::keymap::Action,
+        fs::{create_dir, create_file, expand_path},
+        i18n::Lang,
+        split_args,
+    },
+};
+
+type CommandResult = Result<Option<Action>, String>;
+
+pub struct Command {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub usage: &'static str,
+    pub min_args: usize,
+    pub max_args: Option<usize>,
+    run: fn(&mut State, &[String]) -> CommandResult,
+}
+
+pub static COMMANDS: &[Command] = &[
+    Command {
+        name: "cd",
+        aliases: &[],
+        usage: "cd [path]",
+        min_args: 0,
+        max_args: Some(1),
+        run: cd,
+    },
+    Command {
+        name: "mkdir",
+        aliases: &[],
+        usage: "mkdir <name>...",
+        min_args: 1,
+        max_args: None,
+        run: mkdir,
+    },
+    Command {
+        name: "touch",
+        aliases: &[],
+        usage: "touch <name>...",
+        min_args: 1,
+        max_args: None,
+        run: touch,
+    },
+    Command {
+        name: "rename",
+        aliases: &[],
+        usage: "rename <new name>",
+        min_args: 1,
+        max_args: Some(1),
+        run: rename,
+    },
+    Command {
+        name: "set",
+        aliases: &[],
+        usage: "set <[no]hidden[!]|search=<engine>>...",
+        min_args: 1,
+        max_args: None,
+        run: set,
+    },
+    Command {
+        name: "sort",
+        aliases: &[],
+        usage: "sort [-r] <name|mtime|size|ext>",
+        min_args: 1,
+        max_args: Some(2),
+        run: sort,
+    },
+    Command {
+        name: "setlocal",
+        aliases: &["setl"],
+        usage: SETLOCAL_USAGE,
+        min_args: 1,
+        max_args: Some(3),
+        run: setlocal,
+    },
+    Command {
+        name: "bookmark",
+        aliases: &[],
+        usage: "bookmark <add|delete|open> <name>",
+        min_args: 2,
+        max_args: Some(2),
+        run: bookmark,
+    },
+    Command {
+        name: "find",
+        aliases: &[],
+        usage: "find [name] [size<N|size>N] [mtime<AGE|mtime>AGE]...",
+        min_args: 0,
+        max_args: None,
+        run: |state, args| {
+            if args.is_empty() {
+                state.close_find();
+                return Ok(None);
+            }
+            state.find_files(args)?;
+            Ok(None)
+        },
+    },
+    Command {
+        name: "jumps",
+        aliases: &["ju"],
+        usage: "jumps",
+        min_args: 0,
+        max_args: Some(0),
+        run: |_, _| Ok(Some(Action::Jumps)),
+    },
+    Command {
+        name: "z",
+        aliases: &[],
+        usage: "z <keyword>...",
+        min_args: 1,
+        max_args: None,
+        run: |state, args| {
+            let keywords: Vec<&str> = args.iter().map(String::as_str).collect();
+            state.z_jump(&keywords).map_err(|e| e.to_string())?;
+            Ok(None)
+        },
+    },
+    Command {
+        name: "tabnew",
+        aliases: &[],
+        usage: "tabnew [path]",
+        min_args: 0,
+        max_args: Some(1),
+        run: tabnew,
+    },
+    Command {
+        name: "tabclose",
+        aliases: &["tabc"],
+        usage: "tabclose",
+        min_args: 0,
+        max_args: Some(0),
+        run: |state, _| {
+            state.close_tab();
+            Ok(None)
+        },
+    },
+    Command {
+        name: "tabnext",
+        aliases: &["tabn"],
+        usage: "tabnext [number]",
+        min_args: 0,
+        max_args: Some(1),
+        run: tabnext,
+    },
+    Command {
+        name: "tabprevious",
+        aliases: &["tabp"],
+        usage: "tabprevious",
+        min_args: 0,
+        max_args: Some(0),
+        run: |state, _| {
+            state.next_tab(-1);
+            Ok(None)
+        },
+    },
+    Command {
+        name: "tabmove",
+        aliases: &["tabm"],
+        usage: "tabmove <+n|-n|n>",
+        min_args: 1,
+        max_args: Some(1),
+        run: tabmove,
+    },
+    Command {
+        name: "reload",
+        aliases: &[],
+        usage: "reload",
+        min_args: 0,
+        max_args: Some(0),
+        run: |state, _| {
+            state.reload_config();
+            Ok(None)
+        },
+    },
+    Command {
+        name: "yank",
+        aliases: &[],
+        usage: "yank",
+        min_args: 0,
+        max_args: Some(0),
+        run: |_, _| Ok(Some(Action::Yank)),
+    },
+    Command {
+        name: "cut",
+        aliases: &[],
+        usage: "cut",
+        min_args: 0,
+        max_args: Some(0),
+        run: |_, _| Ok(Some(Action::Cut)),
+    },
+    Command {
+        name: "paste",
+        aliases: &[],
+        usage: "paste",
+        min_args: 0,
+        max_args: Some(0),
+        run: |_, _| Ok(Some(Action::Paste)),
+    },
+    Command {
+        name: "trash",
+        aliases: &[],
+        usage: "trash",
+        min_args: 0,
+        max_args: Some(0),
+        run: |_, _| Ok(Some(Action::Trash)),
+    },
+    Command {
+        name: "delete",
+        aliases: &[],
+        usage: "delete",
+        min_args: 0,
+        max_args: Some(0),
+        run: |_, _| Ok(Some(Action::Delete)),
+    },
+    Command {
+        name: "quit",
+        aliases: &["q"],
+        usage: "quit",
+        min_args: 0,
+        max_args: Some(0),
+        run: |_, _| Ok(Some(Action::Quit)),
+    },
+];
+
+pub fn find_command(name: &str) -> Option<&'static Command> {
+    COMMANDS
+        .iter()
+        .find(|c| c.name == name || c.aliases.contains(&name))
+}
+
+pub fn complete_command_name(prefix: &str) -> Vec<&'static str> {
+    COMMANDS
+        .iter()
+        .flat_map(|c| std::iter::once(c.name).chain(c.aliases.iter().copied()))
+        .filter(|name| name.starts_with(prefix))
+        .collect()
+}
+
+pub fn run_command(state: &mut State, line: &str) -> CommandResult {
+    let args = split_args(line)?;
+    let Some((name, args)) = args.split_first() else {
+        return Ok(None);
+    };
+    let command = find_command(name).ok_or_else(|| Lang::en_fmt("unknown_command", &[name]))?;
+
+    if args.len() < command.min_args || command.max_args.is_some_and(|max| args.len() > max) {
+        return Err(Lang::en_fmt("command_usage", &[command.usage]));
+    }
+    (command.run)(state, args)
+}
+
+fn cd(state: &mut State, args: &[String]) -> CommandResult {
+    let target = args.first().map(String::as_str).unwrap_or("~");
+    let dir = expand_path(target, &state.current_dir);
+    state.change_dir(dir).map_err(|e| e.to_string())?;
+    Ok(None)
+}
+
+fn mkdir(state: &mut State, args: &[String]) -> CommandResult {
+    for name in args {
+        create_dir(name.clone(), &state.current_dir).map_err(|e| e.to_string())?;
+    }
+    let position_id = get_position(&state.positions_map, &state.current_dir);
+    let _ = state.reset_state_after_changes(position_id);
+    Ok(None)
+}
+
+fn touch(state: &mut State, args: &[String]) -> CommandResult {
+    for name in args {
+        create_file(name.clone(), &state.current_dir).map_err(|e| e.to_string())?;
+    }
+    let position_id = get_position(&state.positions_map, &state.current_dir);
+    let _ = state.reset_state_after_changes(position_id);
+    Ok(None)
+}
+
+fn rename(state: &mut State, args: &[String]) -> CommandResult {
+    state
+        .rename_selected(args[0].clone())
+        .map_err(|e| e.to_string())?;
+    let position_id = get_position(&state.positions_map, &state.current_dir);
+    let _ = state.reset_state_after_changes(position_id);
+    Ok(None)
+}
+
+fn set(state: &mut State, args: &[String]) -> CommandResult {
+    for arg in args {
+        if let Some((name, value)) = arg.split_once('=') {
+            match name {
+                "search" => {
+                    state.search_engine = Some(
+                        MatchEngine::from_name(value)
+                            .ok_or_else(|| Lang::en_fmt("unknown_engine", &[value]))?,
+                    );
+                }
+                _ => return Err(Lang::en_fmt("unknown_option", &[arg])),
+            }
+            continue;
+        }
+        // vim-style: `opt` enables, `noopt` disables, `opt!` toggles
+        let (name, value) = if let Some(name) = arg.strip_suffix('!') {
+            (name, None)
+        } else if let Some(name) = arg.strip_prefix("no") {
+            (name, Some(false))
+        } else {
+            (arg.as_str(), Some(true))
+        };
+
+        match name {
+            "hidden" => {
+                if value != Some(state.show_hidden_files) {
+                    state.toggle_hidden_files();
+                }
+            }
+            _ => return Err(Lang::en_fmt("unknown_option", &[arg])),
+        }
+    }
+    Ok(None)
+}
+
+fn sort(state: &mut State, args: &[String]) -> CommandResult {
+    let (reverse, key) = match args {
+        [flag, key] if flag == "-r" => (true, key),
+        [key] => (false, key),
+        _ => {
+            return Err(Lang::en_fmt(
+                "command_usage",
+                &["sort [-r] <name|mtime|size|ext>"],
+            ))
+        }
+    };
+    let by = SortBy::from_name(key).ok_or_else(|| Lang::en_fmt("unknown_sort", &[key]))?;
+    state.sort_files(Sort { by, reverse });
+    Ok(None)
+}
+
+const SETLOCAL_USAGE: &str =
+    "setlocal <[no]hidden[!]|[no]preview[!]|sort [-r] <key>|filter <text>|nofilter|reset>";
+
+/// Like `set`/`sort`, but only for the current directory and remembered across sessions.
+fn setlocal(state: &mut State, args: &[String]) -> CommandResult {
+    let current = state.view_of(&state.current_dir);
+    // same `opt` / `noopt` / `opt!` forms as `set`
+    let flag = |arg: &str, name: &str, value: bool| {
+        if arg == name {
+            Some(true)
+        } else if arg.strip_prefix("no") == Some(name) {
+            Some(false)
+        } else if arg.strip_suffix('!') == Some(name) {
+            Some(!value)
+        } else {
+            None
+        }
+    };
+
+    match args {
+        [arg] if flag(arg, "hidden", current.show_hidden).is_some() => {
+            let hidden = flag(arg, "hidden", current.show_hidden);
+            state.set_local_view(|view| view.hidden = hidden);
+        }
+        [arg] if flag(arg, "preview", current.preview).is_some() => {
+            let preview = flag(arg, "preview", current.preview);
+            state.set_local_view(|view| view.preview = preview);
+        }
+        [cmd, rest @ ..] if cmd == "sort" => {
+            let (reverse, key) = match rest {
+                [flag, key] if flag == "-r" => (true, key),
+                [key] => (false, key),
+                _ => return Err(Lang::en_fmt("command_usage", &[SETLOCAL_USAGE])),
+            };
+            let by = SortBy::from_name(key).ok_or_else(|| Lang::en_fmt("unknown_sort", &[key]))?;
+            state.set_local_view(|view| {
+                view.sort = Some(by);
+                view.reverse = Some(reverse);
+            });
+        }
+        [cmd, filter] if cmd == "filter" => {
+            let filter = filter.clone();
+            state.set_local_view(|view| view.filter = Some(filter));
+        }
+        [cmd] if cmd == "nofilter" => state.set_local_view(|view| view.filter = None),
+        [cmd] if cmd == "reset" => state.set_local_view(|view| *view = DirView::default()),
+        [arg] => return Err(Lang::en_fmt("unknown_option", &[arg])),
+        _ => return Err(Lang::en_fmt("command_usage", &[SETLOCAL_USAGE])),
+    }
+    Ok(None)
+}
+
+fn tabnew(state: &mut State, args: &[String]) -> CommandResult {
+    let dir = args
+        .first()
+        .map(|path| expand_path(path, &state.current_dir));
+    state.new_tab(dir).map_err(|e| e.to_string())?;
+    Ok(None)
+}
+
+fn tabnext(state: &mut State, args: &[String]) -> CommandResult {
+    match args.first() {
+        Some(number) => {
+            let number: usize = number
+                .parse()
+                .map_err(|_| Lang::en_fmt("command_usage", &["tabnext [number]"]))?;
+            state.switch_tab(number.saturating_sub(1));
+        }
+        None => state.next_tab(1),
+    }
+    Ok(None)
+}
+
+/// `+n`/`-n` move relative to the current place, a bare `n` to the n-th slot.
+fn tabmove(state: &mut State, args: &[String]) -> CommandResult {
+    let usage = || Lang::en_fmt("command_usage", &["tabmove <+n|-n|n>"]);
+    let arg = &args[0];
+    let step = if arg.starts_with(['+', '-']) {
+        arg.parse::<isize>().map_err(|_| usage())?
+    } else {
+        let slot = arg.parse::<usize>().map_err(|_| usage())?;
+        slot.saturating_sub(1) as isize - state.tabs.active as isize
+    };
+    state.move_tab(step);
+    Ok(None)
+}
+
+fn bookmark(state: &mut State, args: &[String]) -> CommandResult {
+    let name = &args[1];
+    match args[0].as_str() {
+        "add" => state.commit_new_bookmark(name.clone()),
+        "delete" => {
+            if !state.delete_bookmark(name) {
+                return Err(Lang::en_fmt("bookmark_not_found", &[name]));
+            }
+        }
+        "open" => {
+            let path: PathBuf = state
+                .config
+                .bookmarks
+                .get(name)
+                .cloned()
+                .ok_or_else(|| Lang::en_fmt("bookmark_not_found", &[name]))?;
+            if path.is_file() {
+                state.execute_file(path);
+            } else {
+                state.change_dir(path).map_err(|e| e.to_string())?;
+            }
+        }
+        _ => {
+            return Err(Lang::en_fmt(
+                "command_usage",
+                &["bookmark <add|delete|open> <name>"],
+            ))
+        }
+    }
+    Ok(None)
+}
+
+pub trait CommandLine {
+    fn open_command_line(&mut self);
+    fn commit_command(&mut self) -> Option<Action>;
+    fn complete_command(&mut self);
+}
+
+impl<'a> CommandLine for State<'a> {
+    fn open_command_line(&mut self) {
+        self.mode = Mode::Insert;
+        self.modal_type = ModalKind::BottomLine {
+            action: BottomLineModalAction::Command,
+        };
+    }
+
+    fn commit_command(&mut self) -> Option<Action> {
+        let line = self.input.lines().join("");
+        self.enter_normal_mode();
+        if self.search_pattern.is_some() {
+            self.mode = Mode::Search;
+        }
+
+        match run_command(self, &line) {
+            Ok(action) => action,
+            Err(msg) => {
+                self.notification = Notification::Error { msg: msg.into() }.into();
+                None
+            }
+        }
+    }
+
+    fn complete_command(&mut self) {
+        let line = self.input.lines().join("");
+        if line.contains(char::is_whitespace) {
+            return;
+        }
+
+        let candidates = complete_command_name(&line);
+        if candidates.is_empty() {
+            return;
+        }
+        let mut completed = common_prefix(&candidates).to_string();
+        if candidates.len() == 1 {
+            completed.push(' ');
+            self.notification = None;
+        } else {
+            self.notification = Notification::Info {
+                msg: candidates.join("  ").into(),
+            }
+            .into();
+        }
+        self.input = TextArea::from([completed]);
+        self.input.move_cursor(CursorMove::End);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::test_utils::create_test_state;
+
+    #[test]
+    fn unknown_command_is_reported() {
+        let mut state = create_test_state();
+        state.open_command_line();
+        state.input = TextArea::from(["frobnicate"]);
+        assert_eq!(state.commit_command(), None);
+        assert!(matches!(
+            state.notification,
+            Some(Notification::Error { .. })
+        ));
+        assert_eq!(state.mode, Mode::Normal);
+    }
+
+    #[test]
+    fn wrong_arity_shows_usage() {
+        let mut state = create_test_state();
+        let err = run_command(&mut state, "rename").unwrap_err();
+        assert_eq!(err, "Usage: rename <new name>");
+    }
+
+    #[test]
+    fn quit_maps_to_action() {
+        let mut state = create_test_state();
+        assert_eq!(run_command(&mut state, "q"), Ok(Some(Action::Quit)));
+    }
+
+    #[test]
+    fn set_hidden_option() {
+        let mut state = create_test_state();
+        assert!(run_command(&mut state, "set hidden").is_ok());
+        assert!(state.show_hidden_files);
+        assert!(run_command(&mut state, "set hidden!").is_ok());
+        assert!(!state.show_hidden_files);
+        assert!(run_command(&mut state, "set bogus").is_err());
+    }
+
+    #[test]
+    fn set_search_engine() {
+        let mut state = create_test_state();
+        assert!(run_command(&mut state, "set search=regex").is_ok());
+        assert_eq!(state.search_engine(), MatchEngine::Regex);
+        assert!(run_command(&mut state, "set search=bogus").is_err());
+        assert_eq!(state.search_engine(), MatchEngine::Regex);
+        // a session setting, never saved with the config
+        assert_eq!(state.config.common.search, MatchEngine::Substring);
+    }
+
+    #[test]
+    fn setlocal_options() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = create_test_state();
+        state.current_dir = dir.path().to_path_buf();
+
+        assert!(run_command(&mut state, "setlocal sort -r size").is_ok());
+        assert!(run_command(&mut state, "setl nopreview").is_ok());
+        assert!(run_command(&mut state, "setlocal hidden!").is_ok());
+        let view = state.view_of(dir.path());
+        assert_eq!(view.sort.by, SortBy::Size);
+        assert!(view.sort.reverse && view.show_hidden && !view.preview);
+        assert_eq!(state.sort, Sort::default());
+
+        assert!(run_command(&mut state, "setlocal reset").is_ok());
+        assert!(state.views.get(dir.path()).is_none());
+        assert!(run_command(&mut state, "setlocal bogus").is_err());
+        assert!(run_command(&mut state, "setlocal sort").is_err());
+    }
+
+    #[test]
+    fn tab_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().canonicalize().unwrap();
+        let mut state = create_test_state();
+
+        assert!(run_command(&mut state, &format!("tabnew {}", dir.display())).is_ok());
+        assert!(run_command(&mut state, "tabnew").is_ok());
+        assert_eq!(state.tabs.len(), 3);
+        assert!(run_command(&mut state, "tabmove 1").is_ok());
+        assert_eq!(state.tabs.active, 0);
+        assert!(run_command(&mut state, "tabm +1").is_ok());
+        assert_eq!(state.tabs.active, 1);
+        assert!(run_command(&mut state, "tabn 3").is_ok());
+        assert_eq!(state.tabs.active, 2);
+        assert!(run_command(&mut state, "tabmove x").is_err());
+    }
+
+    #[test]
+    fn completes_command_names() {
+        let mut state = create_test_state();
+        state.open_command_line();
+        state.input = TextArea::from(["mk"]);
+        state.complete_command();
+        assert_eq!(state.input.lines().join(""), "mkdir ");
+    }
+}
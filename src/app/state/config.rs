@@ -0,0 +1,210 @@
+use std::{fs, time::SystemTime};
+
+use crate::app::{
+    model::{miller::positions::get_position, notification::Notification},
+    state::State,
+    ui::file_preview::Highlighter,
+    utils::{
+        config_parser::{
+            default_config::Config, keymap::Keymap, opener::Openers, reload_config, theme::Theme,
+        },
+        i18n::Lang,
+        ls_colors::LsColors,
+    },
+};
+
+pub fn config_modified(config: &Config) -> Option<SystemTime> {
+    config
+        .path
+        .as_ref()
+        .and_then(|path| fs::metadata(path).ok())
+        .and_then(|meta| meta.modified().ok())
+}
+
+pub trait ConfigReload {
+    fn reload_config(&mut self);
+    fn reload_config_if_changed(&mut self) -> bool;
+}
+
+impl<'a> ConfigReload for State<'a> {
+    /// `:reload` also picks up new or edited syntaxes and themes.
+    fn reload_config(&mut self) {
+        self.apply_config_file(true);
+    }
+
+    fn reload_config_if_changed(&mut self) -> bool {
+        let modified = config_modified(&self.config);
+        if modified.is_none() || modified == self.config_modified {
+            return false;
+        }
+
+        // our own writes (e.g. a new bookmark) bump the mtime without changing anything
+        let unchanged = reload_config(&self.config).is_ok_and(|config| config == self.config);
+        if unchanged {
+            self.config_modified = modified;
+            return false;
+        }
+        self.apply_config_file(false);
+        true
+    }
+}
+
+impl<'a> State<'a> {
+    /// Rebuilds the highlighter when `reload_assets` or when `[preview]` changed.
+    fn apply_config_file(&mut self, reload_assets: bool) {
+        self.config_modified = config_modified(&self.config);
+
+        let config = match reload_config(&self.config) {
+            Ok(config) => config,
+            Err(msg) => {
+                self.notification = Notification::Error { msg: msg.into() }.into();
+                return;
+            }
+        };
+        let resolved = Keymap::from_config(&config.keys).and_then(|keymap| {
+            let theme = Theme::from_config(&config.theme)?;
+            // loading syntaxes is slow, keep the current ones unless asked or the section changed
+            let highlighter = (reload_assets || config.preview != self.config.preview)
+                .then(|| Highlighter::from_config(&config.preview, config.dir()))
+                .transpose()?;
+            let openers = Openers::from_config(&config.openers)?;
+            Ok((keymap, theme, highlighter, openers))
+        });
+        match resolved {
+            Ok((keymap, theme, highlighter, openers)) => {
+                self.config = config;
+                self.keymap = keymap;
+                self.theme = theme;
+                self.openers = openers;
+                if let Some(highlighter) = highlighter {
+                    self.highlighter = highlighter;
+                }
+                self.ls_colors = LsColors::from_config(&self.config.theme);
+                self.pending_keys.clear();
+                let position_id = get_position(&self.positions_map, &self.current_dir);
+                let _ = self.reset_state_except_notifications(position_id);
+                self.notification = Notification::Info {
+                    msg: Lang::en("config_reloaded").into(),
+                }
+                .into();
+            }
+            Err(e) => {
+                self.notification = Notification::Error {
+                    msg: e.to_string().into(),
+                }
+                .into();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::app::{state::Mark, test_utils::create_test_state};
+
+    #[test]
+    fn reload_keeps_navigation_state() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "[common]\neditor = \"hx\"\n[bookmarks]\nwork = \"/work\"\n",
+        )
+        .unwrap();
+
+        let mut state = create_test_state();
+        state.config.path = Some(path);
+        state.mark_item();
+        let current_dir = state.current_dir.clone();
+
+        assert!(state.reload_config_if_changed());
+        assert_eq!(state.config.common.editor, "hx");
+        assert_eq!(
+            state.config.bookmarks.get("work"),
+            Some(&PathBuf::from("/work"))
+        );
+        assert_eq!(state.current_dir, current_dir);
+        assert_eq!(state.marked.len(), 1);
+        assert!(!state.reload_config_if_changed());
+    }
+
+    #[test]
+    fn reload_errors_become_notifications() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "[common]\neditor = \"hx\"\n[bookmarks]\n[keys.normal]\nnope = [\"x\"]\n",
+        )
+        .unwrap();
+
+        let mut state = create_test_state();
+        state.config.path = Some(path);
+        state.reload_config();
+
+        assert!(matches!(
+            state.notification,
+            Some(Notification::Error { .. })
+        ));
+        assert_eq!(state.config.common.editor, "nvim");
+    }
+
+    #[test]
+    fn reload_applies_theme() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "[common]\neditor = \"hx\"\n[bookmarks]\n[theme]\npreset = \"mono\"\n",
+        )
+        .unwrap();
+
+        let mut state = create_test_state();
+        state.config.path = Some(path);
+        state.reload_config();
+
+        assert_eq!(state.theme, Theme::mono());
+    }
+
+    #[test]
+    fn explicit_reload_picks_up_preview_assets() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "[common]\neditor = \"hx\"\n[bookmarks]\n[preview]\ntheme = \"Mine\"\n",
+        )
+        .unwrap();
+        let themes = dir.path().join("themes");
+        fs::create_dir(&themes).unwrap();
+        fs::write(
+            themes.join("Mine.tmTheme"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0"><dict>
+<key>name</key><string>Mine</string>
+<key>settings</key><array><dict><key>settings</key><dict>
+<key>foreground</key><string>#112233</string>
+</dict></dict></array>
+</dict></plist>"#,
+        )
+        .unwrap();
+
+        let mut state = create_test_state();
+        state.config.path = Some(path);
+        state.reload_config();
+        assert_eq!(state.config.preview.theme, "Mine");
+
+        // `[preview]` is unchanged, only the theme it names is gone
+        fs::remove_dir_all(&themes).unwrap();
+        state.reload_config();
+        assert!(matches!(
+            state.notification,
+            Some(Notification::Error { .. })
+        ));
+    }
+}
@@ -0,0 +1,163 @@
+use std::mem;
+
+use crate::app::{
+    model::{
+        clipboard::ClipboardAction, dual_pane::DualPane, miller::columns::MillerColumns,
+        notification::Notification, tabs::Tab,
+    },
+    state::{Mark, Mode, State},
+    utils::i18n::Lang,
+};
+
+pub trait DualPanes {
+    fn toggle_dual_pane(&mut self);
+    fn switch_pane(&mut self);
+    fn transfer_to_other_pane(&mut self, action: ClipboardAction);
+}
+
+impl<'a> DualPanes for State<'a> {
+    /// The second pane opens on the current directory.
+    fn toggle_dual_pane(&mut self) {
+        self.dual_pane = match self.dual_pane.take() {
+            Some(_) => None,
+            None => Some(DualPane {
+                other: Tab {
+                    current_dir: self.current_dir.clone(),
+                    positions_map: self.positions_map.clone(),
+                    ..Tab::default()
+                },
+                other_files: self.files[1].clone(),
+                focus_left: true,
+            }),
+        };
+    }
+
+    fn switch_pane(&mut self) {
+        let Some(mut dual) = self.dual_pane.take() else {
+            return;
+        };
+        let focused = self.take_tab();
+        let other = mem::replace(&mut dual.other, focused);
+        dual.focus_left = !dual.focus_left;
+        self.dual_pane = Some(dual);
+        self.put_tab(other);
+    }
+
+    /// Copies or moves the marked files, or the one under the cursor, into the other pane.
+    fn transfer_to_other_pane(&mut self, action: ClipboardAction) {
+        let Some(dest) = self
+            .dual_pane
+            .as_ref()
+            .map(|dual| dual.other.current_dir.clone())
+        else {
+            self.notification = Notification::Warn {
+                msg: Lang::en("dual_pane_off").into(),
+            }
+            .into();
+            return;
+        };
+        let items = self.selected_paths();
+        if items.is_empty() {
+            return;
+        }
+        self.transfer_files(&items, &action, &dest);
+        self.clear_marks();
+        if matches!(self.mode, Mode::Visual { .. }) {
+            self.mode = Mode::Normal;
+        }
+    }
+}
+
+impl<'a> State<'a> {
+    /// Re-lists the unfocused pane so it reflects changes made from the focused one.
+    pub fn refresh_other_pane(&mut self) {
+        let global_view = self.global_view();
+        if let Some(dual) = &mut self.dual_pane {
+            dual.other_files =
+                MillerColumns::list_dir(&dual.other.current_dir, &self.views, &global_view)
+                    .unwrap_or_default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::app::{state::Navigation, test_utils::create_test_state};
+
+    #[test]
+    fn panes_keep_their_own_dirs() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir(root.join("dest")).unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root.clone()).unwrap();
+        state.toggle_dual_pane();
+        state.change_dir(root.join("dest")).unwrap();
+
+        state.switch_pane();
+        assert_eq!(state.current_dir, root);
+        let dual = state.dual_pane.as_ref().unwrap();
+        assert_eq!(dual.other.current_dir, root.join("dest"));
+        assert!(!dual.focus_left);
+
+        state.toggle_dual_pane();
+        assert!(state.dual_pane.is_none());
+    }
+
+    #[test]
+    fn copies_and_moves_to_other_pane() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir(root.join("dest")).unwrap();
+        fs::write(root.join("a.txt"), "a").unwrap();
+        fs::write(root.join("b.txt"), "b").unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root.join("dest")).unwrap();
+        state.toggle_dual_pane();
+        state.change_dir(root.clone()).unwrap();
+
+        // entries: dest/, a.txt, b.txt
+        state.navigate_down(1).unwrap();
+        state.transfer_to_other_pane(ClipboardAction::Copy);
+        assert!(root.join("a.txt").exists());
+        assert!(root.join("dest/a.txt").exists());
+
+        state.navigate_down(1).unwrap();
+        state.transfer_to_other_pane(ClipboardAction::Cut);
+        assert!(!root.join("b.txt").exists());
+        assert!(root.join("dest/b.txt").exists());
+
+        let other_files = &state.dual_pane.as_ref().unwrap().other_files;
+        assert_eq!(other_files.len(), 2);
+    }
+
+    #[test]
+    fn failed_move_keeps_the_source() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("dest")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/kept.txt"), "kept").unwrap();
+        // a dangling link cannot be copied, so the copy of `src` fails
+        std::os::unix::fs::symlink(root.join("missing"), root.join("src/link")).unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root.join("dest")).unwrap();
+        state.toggle_dual_pane();
+        state.change_dir(root.clone()).unwrap();
+
+        // entries: dest/, src/
+        state.navigate_down(1).unwrap();
+        state.transfer_to_other_pane(ClipboardAction::Cut);
+        assert!(root.join("src/kept.txt").exists());
+        assert!(matches!(
+            state.notification,
+            Some(Notification::Warn { .. })
+        ));
+    }
+}
@@ -1,18 +1,21 @@
-use std::{io, path::PathBuf};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
 
 use crate::app::{
     model::{
         clipboard::{Clipboard, ClipboardAction},
         file::{build_full_path, get_current_file},
-        miller::{columns::MillerColumns, positions::get_position},
+        miller::{columns::MillerColumns, positions::get_position, sort::Sort},
         notification::Notification,
     },
-    state::{Bookmarks, HintBar, Mark, State},
+    state::{Bookmarks, HintBar, Mark, Picker, State},
     ui::modal::{ModalKind, UnderLineModalAction},
     utils::{
         fs::{
-            copy_file_path, create_dir, create_file, exec, exec_shell_in, paste_file, remove_file,
-            remove_file_to_trash, rename_file,
+            copy_file_path, create_dir, create_file, exec_shell_in, open_with, paste_file,
+            remove_file, remove_file_to_trash, rename_file,
         },
         i18n::Lang,
     },
@@ -33,6 +36,7 @@ pub trait FileManager {
     fn execute_file(&mut self, file_name: PathBuf);
     fn switch_to_current_dir(&self);
     fn toggle_hidden_files(&mut self);
+    fn sort_files(&mut self, sort: Sort);
 }
 
 impl<'a> FileManager for State<'a> {
@@ -55,6 +59,7 @@ impl<'a> FileManager for State<'a> {
 
     fn commit_changes(&mut self) {
         let input_value = self.input.lines().join("");
+        let mut failure = None;
         if let ModalKind::UnderLine { action } = &self.modal_type {
             match action {
                 UnderLineModalAction::Add => {
@@ -65,22 +70,12 @@ impl<'a> FileManager for State<'a> {
                     } else {
                         let _ = create_file(input_value, &self.current_dir);
                     }
-                    let _ = self.reset_state_except_notifications(0);
+                    let _ = self.reset_state_after_changes(0);
                 }
                 UnderLineModalAction::Edit => {
-                    let current_file =
-                        get_current_file(&self.positions_map, &self.current_dir, &self.files[1]);
-                    if let Some(file) = current_file {
-                        let full_path = build_full_path(&self.current_dir, file);
-                        let _ = rename_file(&full_path, input_value);
-                        let positiond_id = get_position(&self.positions_map, &self.current_dir);
-                        let _ = self.reset_state_except_notifications(positiond_id);
-                    } else {
-                        self.notification = Some(Notification::Error {
-                            msg: format!("Failed to update file: {}", self.current_dir.display())
-                                .into(),
-                        });
-                    }
+                    failure = self.rename_selected(input_value).err();
+                    let positiond_id = get_position(&self.positions_map, &self.current_dir);
+                    let _ = self.reset_state_after_changes(positiond_id);
                 }
                 UnderLineModalAction::Bookmarks => {
                     let input_value = self.input.lines().join("");
@@ -91,6 +86,12 @@ impl<'a> FileManager for State<'a> {
 
         self.enter_normal_mode();
         self.setup_default_input();
+        if let Some(e) = failure {
+            self.notification = Notification::Error {
+                msg: Lang::en_fmt("rename_failed", &[&e.to_string()]).into(),
+            }
+            .into();
+        }
     }
 
     fn copy_files(&mut self, action: ClipboardAction) {
@@ -184,71 +185,14 @@ impl<'a> FileManager for State<'a> {
         }
         self.clear_marks();
         let position_id = get_position(&self.positions_map, &self.current_dir);
-        let _ = self.reset_state_except_notifications(position_id.saturating_sub(1));
+        let _ = self.reset_state_after_changes(position_id.saturating_sub(1));
     }
 
     fn paste_files(&mut self) -> io::Result<()> {
-        match &self.clipboard {
+        match self.clipboard.take() {
             Some(Clipboard::File { items, action }) => {
-                let mut copied_files = Vec::new();
-                let mut errors = Vec::new();
-
-                for file in items {
-                    match paste_file(file, &self.current_dir) {
-                        Ok(_) => {
-                            copied_files.push(file.clone());
-                        }
-                        Err(err) => {
-                            errors.push(err);
-                            continue;
-                        }
-                    }
-                }
-
-                if let ClipboardAction::Cut = action {
-                    for file in &copied_files {
-                        if let Err(err) = remove_file(file) {
-                            errors.push(err);
-                        }
-                    }
-                }
-
-                if !errors.is_empty() {
-                    let lang_key_with_err = match action {
-                        ClipboardAction::Copy => "pasted_with_error",
-                        ClipboardAction::Cut => "moved_with_error",
-                        ClipboardAction::Delete => "deleted_with_error",
-                    };
-                    self.notification = Notification::Warn {
-                        msg: Lang::en_fmt(
-                            lang_key_with_err,
-                            &[
-                                &copied_files.len().to_string(),
-                                &errors.len().to_string(),
-                                &errors
-                                    .iter()
-                                    .map(|e| e.to_string())
-                                    .collect::<Vec<_>>()
-                                    .join(", "),
-                            ],
-                        )
-                        .into(),
-                    }
-                    .into();
-                } else {
-                    let lang_key = match action {
-                        ClipboardAction::Copy => "pasted",
-                        ClipboardAction::Cut => "moved",
-                        ClipboardAction::Delete => "deleted",
-                    };
-                    self.notification = Notification::Success {
-                        msg: Lang::en_fmt(lang_key, &[&copied_files.len().to_string()]).into(),
-                    }
-                    .into();
-                }
-                let position_id = get_position(&self.positions_map, &self.current_dir);
-                let _ = self.reset_state_except_notifications(position_id);
-                self.clipboard = None;
+                let dest = self.current_dir.clone();
+                self.transfer_files(&items, &action, &dest);
                 self.clear_marks();
                 Ok(())
             }
@@ -261,10 +205,27 @@ impl<'a> FileManager for State<'a> {
             }
         }
     }
-
     fn execute_file(&mut self, file_name: PathBuf) {
-        let _ = exec(&self.config.common.editor, &[&file_name.to_string_lossy()]);
-        self.from_external_app = true;
+        if self.choose_file(&file_name) {
+            return;
+        }
+        let result = match self.openers.find(&file_name) {
+            Some(opener) => open_with(
+                opener,
+                std::slice::from_ref(&file_name),
+                self.config.editor(),
+            ),
+            None => open::that_detached(&file_name).map(|_| false),
+        };
+        match result {
+            Ok(took_terminal) => self.from_external_app |= took_terminal,
+            Err(e) => {
+                self.notification = Notification::Error {
+                    msg: Lang::en_fmt("open_failed", &[&e.to_string()]).into(),
+                }
+                .into();
+            }
+        }
     }
 
     fn switch_to_current_dir(&self) {
@@ -274,6 +235,87 @@ impl<'a> FileManager for State<'a> {
     fn toggle_hidden_files(&mut self) {
         self.show_hidden_files = !self.show_hidden_files;
         let position_id = get_position(&self.positions_map, &self.current_dir);
-        let _ = self.reset_state_except_notifications(position_id);
+        let _ = self.reset_state_after_changes(position_id);
+    }
+
+    fn sort_files(&mut self, sort: Sort) {
+        self.sort = sort;
+        self.refresh_keeping_selection();
+    }
+}
+
+impl<'a> State<'a> {
+    /// Renames the entry under the cursor. In a find listing the new name is
+    /// taken relative to its root, the same way the entries are shown.
+    pub fn rename_selected(&mut self, new_name: String) -> io::Result<()> {
+        let file = get_current_file(&self.positions_map, &self.current_dir, &self.files[1])
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, Lang::en("items_not_found")))?;
+        let full_path = build_full_path(&self.current_dir, file);
+        match &self.find {
+            Some(find) => std::fs::rename(&full_path, find.root.join(new_name)),
+            None => rename_file(&full_path, new_name),
+        }
+    }
+
+    /// Copies or moves `items` into `dest` with `paste_file` and reports the outcome.
+    pub fn transfer_files(&mut self, items: &[PathBuf], action: &ClipboardAction, dest: &Path) {
+        let mut copied_files = Vec::new();
+        let mut errors = Vec::new();
+
+        for file in items {
+            match paste_file(file, dest) {
+                Ok(_) => {
+                    copied_files.push(file.clone());
+                }
+                Err(err) => {
+                    errors.push(err);
+                    continue;
+                }
+            }
+        }
+
+        if let ClipboardAction::Cut = action {
+            for file in &copied_files {
+                if let Err(err) = remove_file(file) {
+                    errors.push(err);
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            let lang_key_with_err = match action {
+                ClipboardAction::Copy => "pasted_with_error",
+                ClipboardAction::Cut => "moved_with_error",
+                ClipboardAction::Delete => "deleted_with_error",
+            };
+            self.notification = Notification::Warn {
+                msg: Lang::en_fmt(
+                    lang_key_with_err,
+                    &[
+                        &copied_files.len().to_string(),
+                        &errors.len().to_string(),
+                        &errors
+                            .iter()
+                            .map(|e| e.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ],
+                )
+                .into(),
+            }
+            .into();
+        } else {
+            let lang_key = match action {
+                ClipboardAction::Copy => "pasted",
+                ClipboardAction::Cut => "moved",
+                ClipboardAction::Delete => "deleted",
+            };
+            self.notification = Notification::Success {
+                msg: Lang::en_fmt(lang_key, &[&copied_files.len().to_string()]).into(),
+            }
+            .into();
+        }
+        let position_id = get_position(&self.positions_map, &self.current_dir);
+        let _ = self.reset_state_after_changes(position_id);
     }
 }
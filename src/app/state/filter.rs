@@ -0,0 +1,106 @@
+use crate::app::{
+    model::{matcher::Matcher, notification::Notification},
+    state::{Mode, State},
+    ui::modal::{BottomLineModalAction, ModalKind},
+};
+
+pub trait Filter {
+    fn open_filter(&mut self);
+    fn commit_filter(&mut self);
+    fn set_filter(&mut self, query: &str) -> Result<(), String>;
+    fn clear_filter(&mut self);
+}
+
+impl<'a> Filter for State<'a> {
+    fn open_filter(&mut self) {
+        self.mode = Mode::Insert;
+        self.setup_default_input();
+        if let Some(matcher) = self.views.session_filter(&self.current_dir) {
+            self.input.insert_str(matcher.query());
+        }
+        self.modal_type = ModalKind::BottomLine {
+            action: BottomLineModalAction::Filter,
+        };
+    }
+
+    fn commit_filter(&mut self) {
+        let query = self.input.lines().join("");
+        self.enter_normal_mode();
+        if self.search_pattern.is_some() {
+            self.mode = Mode::Search;
+        }
+        if let Err(msg) = self.set_filter(&query) {
+            self.notification = Notification::Error { msg: msg.into() }.into();
+        }
+    }
+
+    /// Hides the entries of the current directory that `query` does not match,
+    /// keeping the cursor on the same file when it is still listed.
+    fn set_filter(&mut self, query: &str) -> Result<(), String> {
+        let matcher = if query.is_empty() {
+            None
+        } else {
+            Some(Matcher::new(query, self.search_engine())?)
+        };
+        self.views.set_session_filter(&self.current_dir, matcher);
+        self.refresh_keeping_selection();
+        Ok(())
+    }
+
+    fn clear_filter(&mut self) {
+        if self.views.session_filter(&self.current_dir).is_some() {
+            self.views.set_session_filter(&self.current_dir, None);
+            self.refresh_keeping_selection();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::app::{
+        model::miller::positions::get_position,
+        state::{Navigation, Search},
+        test_utils::create_test_state,
+    };
+
+    fn names(state: &State) -> Vec<String> {
+        state.files[1].iter().map(|f| f.name.clone()).collect()
+    }
+
+    #[test]
+    fn filter_narrows_listing_and_keeps_cursor() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir(root.join("sub")).unwrap();
+        for name in ["app.log", "db.log", "notes.txt"] {
+            fs::write(root.join(name), "").unwrap();
+        }
+        let mut state = create_test_state();
+        state.change_dir(root.clone()).unwrap();
+        state.select_path(&root.join("db.log")).unwrap();
+
+        state.set_filter("log").unwrap();
+        assert_eq!(names(&state), ["app.log", "db.log"]);
+        assert_eq!(get_position(&state.positions_map, &root), 1);
+
+        // survives a trip through a subdirectory and searching
+        state.change_dir(root.join("sub")).unwrap();
+        state.navigate_to_parent().unwrap();
+        state.exit_search_mode();
+        assert_eq!(names(&state), ["app.log", "db.log"]);
+
+        let selected = |state: &State| {
+            state.files[1][get_position(&state.positions_map, &root)]
+                .name
+                .clone()
+        };
+        state.navigate_down(1).unwrap();
+        assert_eq!(selected(&state), "db.log");
+        state.clear_filter();
+        assert_eq!(names(&state), ["sub", "app.log", "db.log", "notes.txt"]);
+        assert_eq!(selected(&state), "db.log");
+    }
+}
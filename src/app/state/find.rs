@@ -0,0 +1,162 @@
+use crate::app::{
+    model::{
+        find::{FindListing, FindQuery},
+        miller::positions::{get_position, update_dir_position},
+        notification::Notification,
+    },
+    state::{Mark, State},
+    utils::i18n::Lang,
+};
+
+pub trait Find {
+    fn find_files(&mut self, terms: &[String]) -> Result<(), String>;
+    fn close_find(&mut self);
+}
+
+impl<'a> Find for State<'a> {
+    /// Lists everything under the current directory that `terms` match in
+    /// place of its entries, until it is closed or left.
+    fn find_files(&mut self, terms: &[String]) -> Result<(), String> {
+        let query = FindQuery::parse(terms, self.search_engine())?;
+        let origin_position = match self.find.take() {
+            Some(find) if find.root == self.current_dir => find.origin_position,
+            _ => get_position(&self.positions_map, &self.current_dir),
+        };
+        self.clear_marks();
+        self.find = Some(FindListing::new(&self.current_dir, query, origin_position));
+        update_dir_position(&mut self.positions_map, &self.current_dir, 0);
+        self.reset_state_after_changes(0)
+            .map_err(|e| e.to_string())?;
+
+        let found = self.find.as_ref().map_or(0, |find| find.entries.len());
+        self.notification = Notification::Info {
+            msg: Lang::en_fmt("found", &[&found.to_string()]).into(),
+        }
+        .into();
+        Ok(())
+    }
+
+    /// Goes back to the plain listing with the selection it had before.
+    fn close_find(&mut self) {
+        let Some(find) = self.find.take() else {
+            return;
+        };
+        self.clear_marks();
+        update_dir_position(&mut self.positions_map, &find.root, find.origin_position);
+        let _ = self.reset_state(find.origin_position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::app::{
+        model::file::{build_full_path, get_current_file},
+        state::{file_managment::DeleteMode, FileManager, Navigation},
+        test_utils::create_test_state,
+    };
+
+    fn names(state: &State) -> Vec<String> {
+        state.files[1].iter().map(|f| f.name.clone()).collect()
+    }
+
+    #[test]
+    fn file_operations_use_full_paths() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::write(root.join("a/b/one.rs"), "").unwrap();
+        fs::write(root.join("a/two.rs"), "").unwrap();
+        fs::write(root.join("three.txt"), "").unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root.clone()).unwrap();
+        state.find_files(&["*.rs".into()]).unwrap();
+        assert_eq!(names(&state), ["a/b/one.rs", "a/two.rs"]);
+        let file = get_current_file(&state.positions_map, &state.current_dir, &state.files[1]);
+        assert_eq!(
+            build_full_path(&state.current_dir, file.unwrap()),
+            root.join("a/b/one.rs")
+        );
+
+        state.delete_files(DeleteMode::Permanent);
+        assert!(!root.join("a/b/one.rs").exists());
+        assert_eq!(names(&state), ["a/two.rs"]);
+
+        state.mark_item();
+        state.copy_files(crate::app::model::clipboard::ClipboardAction::Copy);
+        state.close_find();
+        assert_eq!(names(&state), ["a", "three.txt"]);
+        state.paste_files().unwrap();
+        assert!(root.join("two.rs").exists());
+    }
+
+    #[test]
+    fn leaving_the_directory_drops_the_listing() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root.clone()).unwrap();
+        state.find_files(&["src".into()]).unwrap();
+        state.navigate_to_child().unwrap();
+        assert_eq!(state.current_dir, root.join("src"));
+        assert!(state.find.is_none());
+        assert_eq!(names(&state), ["main.rs"]);
+    }
+
+    #[test]
+    fn rename_takes_names_as_listed() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        fs::write(root.join("a/two.rs"), "").unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root.clone()).unwrap();
+        state.find_files(&["*.rs".into()]).unwrap();
+        assert_eq!(names(&state), ["a/two.rs"]);
+
+        state.rename_file();
+        state.input.insert_str("a/three.rs");
+        state.commit_changes();
+        assert!(root.join("a/three.rs").exists());
+        assert_eq!(names(&state), ["a/three.rs"]);
+
+        state.rename_file();
+        state.input.insert_str("missing/four.rs");
+        state.commit_changes();
+        assert!(root.join("a/three.rs").exists());
+        assert!(matches!(
+            state.notification,
+            Some(Notification::Error { .. })
+        ));
+    }
+
+    #[test]
+    fn only_file_changes_walk_the_tree_again() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("a")).unwrap();
+        fs::write(root.join("a/one.rs"), "").unwrap();
+        fs::write(root.join("two.rs"), "").unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root.clone()).unwrap();
+        state.find_files(&["*.rs".into()]).unwrap();
+        fs::write(root.join("a/new.rs"), "").unwrap();
+
+        state.mark_item();
+        state.navigate_down(1).unwrap();
+        assert_eq!(names(&state), ["a/one.rs", "two.rs"]);
+
+        // deletes the marked `a/one.rs`
+        state.delete_files(DeleteMode::Permanent);
+        assert_eq!(names(&state), ["a/new.rs", "two.rs"]);
+        assert!(!state.find.as_ref().unwrap().truncated);
+    }
+}
@@ -0,0 +1,135 @@
+use std::{io, mem, path::Path};
+
+use crate::app::{
+    model::{
+        finder::Finder,
+        miller::{columns::MillerColumns, positions::update_dir_position},
+        notification::Notification,
+    },
+    state::{Mode, Navigation, State},
+    ui::modal::ModalKind,
+};
+
+pub trait FuzzyFinder {
+    fn open_finder(&mut self);
+    fn poll_finder(&mut self) -> bool;
+    fn update_finder_query(&mut self);
+    fn finder_move(&mut self, step: isize);
+    fn commit_finder(&mut self);
+    fn close_finder(&mut self);
+}
+
+impl<'a> FuzzyFinder for State<'a> {
+    /// Starts walking the tree under the current directory with its hidden-file setting.
+    fn open_finder(&mut self) {
+        let finder = Finder::spawn(
+            &self.current_dir,
+            self.view_of(&self.current_dir).show_hidden,
+            self.config.common.respect_ignore,
+        );
+        self.mode = Mode::Insert;
+        self.setup_default_input();
+        self.modal_type = ModalKind::Finder { finder };
+    }
+
+    fn poll_finder(&mut self) -> bool {
+        match &mut self.modal_type {
+            ModalKind::Finder { finder } => finder.poll(),
+            _ => false,
+        }
+    }
+
+    fn update_finder_query(&mut self) {
+        let query = self.input.lines().join("");
+        if let ModalKind::Finder { finder } = &mut self.modal_type {
+            finder.set_query(&query);
+        }
+    }
+
+    fn finder_move(&mut self, step: isize) {
+        if let ModalKind::Finder { finder } = &mut self.modal_type {
+            finder.move_selection(step);
+        }
+    }
+
+    fn commit_finder(&mut self) {
+        let ModalKind::Finder { finder } = mem::replace(&mut self.modal_type, ModalKind::Disabled)
+        else {
+            return;
+        };
+        self.enter_normal_mode();
+        let Some(entry) = finder.selected_entry() else {
+            return;
+        };
+        if let Err(e) = self.reveal_path(&finder.root, Path::new(&entry.path)) {
+            self.notification = Notification::Error {
+                msg: e.to_string().into(),
+            }
+            .into();
+        }
+    }
+
+    /// Dropping the finder stops its walker.
+    fn close_finder(&mut self) {
+        self.enter_normal_mode();
+    }
+}
+
+impl<'a> State<'a> {
+    /// Selects `root/relative` inside its parent, leaving every directory on
+    /// the way selected on the next one so `h` walks back up the same path.
+    fn reveal_path(&mut self, root: &Path, relative: &Path) -> io::Result<()> {
+        let global_view = self.global_view();
+        let mut dir = root.to_path_buf();
+        for component in relative.components() {
+            let name = component.as_os_str().to_string_lossy();
+            let entries = MillerColumns::list_dir(&dir, &self.views, &global_view)?;
+            if let Some(position_id) = entries.iter().position(|f| f.name == name) {
+                update_dir_position(&mut self.positions_map, &dir, position_id);
+            }
+            dir.push(component);
+        }
+        match dir.parent() {
+            Some(parent) => self.change_dir(parent.to_path_buf()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, thread, time::Duration};
+
+    use tui_textarea::TextArea;
+
+    use super::*;
+    use crate::app::{model::miller::positions::get_position, test_utils::create_test_state};
+
+    #[test]
+    fn commit_reveals_entry_through_every_level() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("z/src/ui")).unwrap();
+        fs::create_dir(root.join("b")).unwrap();
+        fs::write(root.join("z/src/ui/body.rs"), "").unwrap();
+        fs::write(root.join("z/src/ui/column_widget.rs"), "").unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root.clone()).unwrap();
+        state.open_finder();
+        while matches!(&state.modal_type, ModalKind::Finder { finder } if finder.is_walking()) {
+            state.poll_finder();
+            thread::sleep(Duration::from_millis(5));
+        }
+        state.input = TextArea::from(["colwid"]);
+        state.update_finder_query();
+        state.commit_finder();
+
+        assert_eq!(state.mode, Mode::Normal);
+        assert_eq!(state.current_dir, root.join("z/src/ui"));
+        assert_eq!(get_position(&state.positions_map, &state.current_dir), 1);
+        assert_eq!(get_position(&state.positions_map, &root), 1);
+        assert_eq!(get_position(&state.positions_map, &root.join("z/src")), 0);
+        assert_eq!(state.jumps.entries().last(), Some(&root));
+    }
+}
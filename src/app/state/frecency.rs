@@ -0,0 +1,107 @@
+use std::{
+    io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::app::{
+    model::notification::Notification,
+    state::{Mode, Navigation, State},
+    ui::modal::{BottomLineModalAction, ModalKind},
+    utils::i18n::Lang,
+};
+
+pub trait FrecencyJump {
+    fn open_z_prompt(&mut self);
+    fn commit_z_prompt(&mut self);
+    fn z_jump(&mut self, keywords: &[&str]) -> io::Result<()>;
+}
+
+impl<'a> FrecencyJump for State<'a> {
+    fn open_z_prompt(&mut self) {
+        self.mode = Mode::Insert;
+        self.setup_default_input();
+        self.modal_type = ModalKind::BottomLine {
+            action: BottomLineModalAction::Frecency,
+        };
+    }
+
+    fn commit_z_prompt(&mut self) {
+        let line = self.input.lines().join("");
+        self.enter_normal_mode();
+        let keywords: Vec<&str> = line.split_whitespace().collect();
+        if keywords.is_empty() {
+            return;
+        }
+        if let Err(e) = self.z_jump(&keywords) {
+            self.notification = Notification::Error {
+                msg: e.to_string().into(),
+            }
+            .into();
+        }
+    }
+
+    /// Goes to the best ranked directory whose path contains the keywords in order.
+    fn z_jump(&mut self, keywords: &[&str]) -> io::Result<()> {
+        let target = self
+            .frecency
+            .best_match(keywords, &self.current_dir, unix_now())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    Lang::en_fmt("no_frecent_match", &[&keywords.join(" ")]),
+                )
+            })?;
+        self.change_dir(target)
+    }
+}
+
+impl<'a> State<'a> {
+    /// Counts a visit to the current directory.
+    pub fn record_visit(&mut self) {
+        self.frecency.record(&self.current_dir, unix_now());
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::app::test_utils::create_test_state;
+
+    #[test]
+    fn visited_dirs_are_jump_targets() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("web/api")).unwrap();
+        fs::create_dir_all(root.join("cli/api")).unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root.join("web/api")).unwrap();
+        state.change_dir(root.join("web/api")).unwrap();
+        state.change_dir(root.join("cli/api")).unwrap();
+        state.change_dir(root.clone()).unwrap();
+        assert_eq!(state.frecency.get(&root.join("web/api")).unwrap().rank, 2.0);
+
+        state.z_jump(&["api"]).unwrap();
+        assert_eq!(state.current_dir, root.join("web/api"));
+        state.z_jump(&["cli", "api"]).unwrap();
+        assert_eq!(state.current_dir, root.join("cli/api"));
+
+        state.open_z_prompt();
+        state.input = tui_textarea::TextArea::from(["missing"]);
+        state.commit_z_prompt();
+        assert_eq!(state.current_dir, root.join("cli/api"));
+        assert!(matches!(
+            state.notification,
+            Some(Notification::Error { .. })
+        ));
+    }
+}
@@ -0,0 +1,191 @@
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::app::{
+    model::notification::Notification,
+    state::{Mode, Navigation, State},
+    ui::modal::{BottomLineModalAction, ModalKind},
+    utils::{
+        common_prefix,
+        fs::{complete_dir_path, expand_path},
+    },
+};
+
+/// Directory candidates of the last Tab press in the go-to prompt.
+#[derive(Debug, PartialEq, Default)]
+pub struct PathCompletion {
+    /// Directory part of the input the candidates are appended to
+    pub dir_part: String,
+    pub candidates: Vec<String>,
+    pub selected: Option<usize>,
+    /// Input right after the completion; typing anything else starts over
+    pub line: String,
+}
+
+impl PathCompletion {
+    /// Candidates still matching what is in the prompt.
+    pub fn visible(&self, line: &str) -> &[String] {
+        if self.line == line {
+            &self.candidates
+        } else {
+            &[]
+        }
+    }
+}
+
+pub trait GoTo {
+    fn open_goto_prompt(&mut self);
+    fn complete_goto(&mut self, forward: bool);
+    fn commit_goto(&mut self);
+}
+
+impl<'a> GoTo for State<'a> {
+    fn open_goto_prompt(&mut self) {
+        self.mode = Mode::Insert;
+        self.setup_default_input();
+        self.modal_type = ModalKind::BottomLine {
+            action: BottomLineModalAction::GoTo {
+                completion: PathCompletion::default(),
+            },
+        };
+    }
+
+    /// First press completes the common prefix; further presses cycle through the candidates.
+    fn complete_goto(&mut self, forward: bool) {
+        let line = self.input.lines().join("");
+        let ModalKind::BottomLine {
+            action: BottomLineModalAction::GoTo { completion },
+        } = &mut self.modal_type
+        else {
+            return;
+        };
+
+        let len = completion.candidates.len();
+        let completed = if completion.line == line && len > 1 {
+            let next = match (completion.selected, forward) {
+                (None, true) => 0,
+                (None, false) => len - 1,
+                (Some(i), true) => (i + 1) % len,
+                (Some(i), false) => (i + len - 1) % len,
+            };
+            completion.selected = Some(next);
+            format!("{}{}/", completion.dir_part, completion.candidates[next])
+        } else {
+            let (dir_part, candidates) =
+                complete_dir_path(&line, &self.current_dir, self.show_hidden_files);
+            let completed = match candidates.as_slice() {
+                [] => return,
+                [only] => format!("{}{}/", dir_part, only),
+                _ => {
+                    let names: Vec<&str> = candidates.iter().map(String::as_str).collect();
+                    format!("{}{}", dir_part, common_prefix(&names))
+                }
+            };
+            *completion = PathCompletion {
+                dir_part,
+                candidates: if candidates.len() > 1 {
+                    candidates
+                } else {
+                    vec![]
+                },
+                selected: None,
+                line: String::new(),
+            };
+            completed
+        };
+
+        completion.line = completed.clone();
+        self.input = TextArea::from([completed]);
+        self.input.move_cursor(CursorMove::End);
+    }
+
+    /// Directories are entered; a file is selected inside its parent.
+    fn commit_goto(&mut self) {
+        let line = self.input.lines().join("");
+        self.enter_normal_mode();
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+
+        let path = expand_path(line, &self.current_dir);
+        let result = if path.is_file() {
+            self.select_path(&path)
+        } else {
+            self.change_dir(path)
+        };
+
+        if let Err(e) = result {
+            self.notification = Notification::Error {
+                msg: e.to_string().into(),
+            }
+            .into();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::app::{model::miller::positions::get_position, test_utils::create_test_state};
+
+    fn type_line(state: &mut State, line: &str) {
+        state.input = TextArea::from([line]);
+        state.input.move_cursor(CursorMove::End);
+    }
+
+    #[test]
+    fn completes_and_cycles_dirs() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir_all(root.path().join("build/debug")).unwrap();
+        fs::create_dir(root.path().join("builds")).unwrap();
+        fs::write(root.path().join("build.rs"), "").unwrap();
+
+        let mut state = create_test_state();
+        state.current_dir = root.path().to_path_buf();
+        state.open_goto_prompt();
+
+        type_line(&mut state, "bu");
+        state.complete_goto(true);
+        assert_eq!(state.input.lines().join(""), "build");
+
+        state.complete_goto(true);
+        assert_eq!(state.input.lines().join(""), "build/");
+        state.complete_goto(true);
+        assert_eq!(state.input.lines().join(""), "builds/");
+        state.complete_goto(false);
+        assert_eq!(state.input.lines().join(""), "build/");
+
+        type_line(&mut state, "build/d");
+        state.complete_goto(true);
+        assert_eq!(state.input.lines().join(""), "build/debug/");
+    }
+
+    #[test]
+    fn file_target_selects_it_in_parent() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir(root.join("src")).unwrap();
+        fs::write(root.join("src/a.rs"), "").unwrap();
+        fs::write(root.join("src/b.rs"), "").unwrap();
+
+        let mut state = create_test_state();
+        state.current_dir = root.clone();
+        state.open_goto_prompt();
+        type_line(&mut state, "src/b.rs");
+        state.commit_goto();
+
+        assert_eq!(state.current_dir, root.join("src"));
+        assert_eq!(get_position(&state.positions_map, &state.current_dir), 1);
+        assert_eq!(state.mode, Mode::Normal);
+
+        state.open_goto_prompt();
+        type_line(&mut state, "missing");
+        state.commit_goto();
+        assert!(matches!(
+            state.notification,
+            Some(Notification::Error { .. })
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::app::{
+    model::{
+        grep::Grep,
+        matcher::{MatchEngine, Matcher},
+        notification::Notification,
+    },
+    state::{Mode, State},
+    ui::modal::{BottomLineModalAction, ModalKind},
+    utils::{fs::exec, i18n::Lang},
+};
+
+pub trait ContentSearch {
+    fn open_grep_prompt(&mut self);
+    fn commit_grep(&mut self);
+    fn poll_grep(&mut self) -> bool;
+    fn grep_move(&mut self, step: isize);
+    fn open_grep_hit(&mut self);
+    fn close_grep(&mut self);
+}
+
+impl<'a> ContentSearch for State<'a> {
+    fn open_grep_prompt(&mut self) {
+        self.mode = Mode::Insert;
+        self.setup_default_input();
+        self.modal_type = ModalKind::BottomLine {
+            action: BottomLineModalAction::Grep,
+        };
+    }
+
+    /// Starts searching the files under the current directory; lines are
+    /// matched as regexes when that is the search engine, otherwise as text.
+    fn commit_grep(&mut self) {
+        let query = self.input.lines().join("");
+        self.enter_normal_mode();
+        if query.is_empty() {
+            return;
+        }
+        let engine = match self.search_engine() {
+            MatchEngine::Regex => MatchEngine::Regex,
+            _ => MatchEngine::Substring,
+        };
+        let matcher = match Matcher::new(&query, engine) {
+            Ok(matcher) => matcher,
+            Err(msg) => {
+                self.notification = Notification::Error { msg: msg.into() }.into();
+                return;
+            }
+        };
+        let grep = Grep::spawn(
+            &self.current_dir,
+            matcher,
+            self.view_of(&self.current_dir).show_hidden,
+            self.config.common.respect_ignore,
+        );
+        self.modal_type = ModalKind::Grep { grep };
+    }
+
+    fn poll_grep(&mut self) -> bool {
+        match &mut self.modal_type {
+            ModalKind::Grep { grep } => grep.poll(),
+            _ => false,
+        }
+    }
+
+    fn grep_move(&mut self, step: isize) {
+        if let ModalKind::Grep { grep } = &mut self.modal_type {
+            grep.move_selection(step);
+        }
+    }
+
+    /// Opens the editor at the selected line; the results stay up for the next one.
+    fn open_grep_hit(&mut self) {
+        let ModalKind::Grep { grep } = &self.modal_type else {
+            return;
+        };
+        let Some(hit) = grep.selected_hit() else {
+            return;
+        };
+        let path = grep.root.join(&hit.path);
+        let line = format!("+{}", hit.line);
+        match exec(self.config.editor(), &[&line, &path.to_string_lossy()]) {
+            Ok(()) => self.from_external_app = true,
+            Err(e) => {
+                self.notification = Notification::Error {
+                    msg: Lang::en_fmt("open_failed", &[&e.to_string()]).into(),
+                }
+                .into();
+            }
+        }
+    }
+
+    /// Dropping the results stops the search.
+    fn close_grep(&mut self) {
+        self.enter_normal_mode();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::app::{state::Navigation, test_utils::create_test_state};
+
+    #[test]
+    fn grep_results_replace_the_prompt() {
+        let root = tempfile::tempdir().unwrap();
+        fs::write(root.path().join("a.txt"), "one\nneedle\n").unwrap();
+        let mut state = create_test_state();
+        state.change_dir(root.path().to_path_buf()).unwrap();
+
+        state.open_grep_prompt();
+        state.commit_grep();
+        assert!(state.modal_type.is_disabled());
+
+        state.open_grep_prompt();
+        state.input.insert_str("needle");
+        state.commit_grep();
+        while matches!(&state.modal_type, ModalKind::Grep { grep } if grep.is_searching()) {
+            state.poll_grep();
+        }
+        let ModalKind::Grep { grep } = &state.modal_type else {
+            panic!("results are not shown");
+        };
+        assert_eq!(grep.selected_hit().unwrap().label(), "a.txt:2:needle");
+        assert_eq!(state.mode, Mode::Normal);
+
+        state.close_grep();
+        assert!(state.modal_type.is_disabled());
+    }
+}
@@ -0,0 +1,124 @@
+use std::path::PathBuf;
+
+use crate::app::{
+    model::notification::Notification, state::State, ui::modal::ModalKind, utils::i18n::Lang,
+};
+
+pub trait Jumps {
+    fn jump_back(&mut self, count: usize);
+    fn jump_forward(&mut self, count: usize);
+    fn jumps_menu(&mut self);
+    fn jumps_move(&mut self, step: isize);
+    fn commit_jump(&mut self);
+    fn close_jumps(&mut self);
+}
+
+impl<'a> Jumps for State<'a> {
+    fn jump_back(&mut self, count: usize) {
+        let target = self.jumps.back(&self.current_dir, count);
+        self.visit_jump(target);
+    }
+
+    fn jump_forward(&mut self, count: usize) {
+        let target = self.jumps.forward(count);
+        self.visit_jump(target);
+    }
+
+    fn jumps_menu(&mut self) {
+        let len = self.jumps.entries().len();
+        if len == 0 {
+            self.notification = Notification::Info {
+                msg: Lang::en("no_jumps").into(),
+            }
+            .into();
+            return;
+        }
+        let selected = self.jumps.current().unwrap_or(len - 1);
+        self.modal_type = ModalKind::Jumps { selected };
+    }
+
+    fn jumps_move(&mut self, step: isize) {
+        let last = self.jumps.entries().len().saturating_sub(1);
+        if let ModalKind::Jumps { selected } = &mut self.modal_type {
+            *selected = selected.saturating_add_signed(step).min(last);
+        }
+    }
+
+    fn commit_jump(&mut self) {
+        let ModalKind::Jumps { selected } =
+            std::mem::replace(&mut self.modal_type, ModalKind::Disabled)
+        else {
+            return;
+        };
+        let target = self.jumps.select(&self.current_dir, selected);
+        self.visit_jump(target);
+    }
+
+    fn close_jumps(&mut self) {
+        self.modal_type = ModalKind::Disabled;
+    }
+}
+
+impl<'a> State<'a> {
+    /// Moves to a jump list entry without recording a new jump.
+    fn visit_jump(&mut self, target: Option<PathBuf>) {
+        if let Some(dir) = target {
+            if let Err(e) = self.enter_dir(dir) {
+                self.notification = Notification::Error {
+                    msg: e.to_string().into(),
+                }
+                .into();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::app::{
+        model::miller::positions::get_position, state::Navigation, test_utils::create_test_state,
+    };
+
+    #[test]
+    fn jumps_restore_dir_and_selection() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        let work = root.join("work");
+        fs::create_dir_all(work.join("deep")).unwrap();
+        fs::write(work.join("a"), "").unwrap();
+        fs::write(work.join("b"), "").unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(work.clone()).unwrap();
+        state.navigate_down(2).unwrap();
+        state.change_dir(root.clone()).unwrap();
+
+        state.jump_back(1);
+        assert_eq!(state.current_dir, work);
+        assert_eq!(get_position(&state.positions_map, &state.current_dir), 2);
+
+        state.jump_forward(1);
+        assert_eq!(state.current_dir, root);
+    }
+
+    #[test]
+    fn picker_selects_entry() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir(root.join("a")).unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root.join("a")).unwrap();
+        state.change_dir(root.clone()).unwrap();
+
+        state.jumps_menu();
+        assert!(matches!(state.modal_type, ModalKind::Jumps { selected: 1 }));
+        state.jumps_move(5);
+        state.commit_jump();
+        assert_eq!(state.current_dir, root.join("a"));
+        assert!(state.modal_type.is_disabled());
+    }
+}
@@ -1,11 +1,12 @@
 use crate::app::{
-    model::file::get_current_file,
+    model::{file::get_current_file, miller::positions::get_position},
     state::{Navigation, State},
 };
 
 pub trait Mark {
     fn mark_item(&mut self);
     fn mark_and_down(&mut self);
+    fn mark_next(&mut self, count: usize);
     fn clear_marks(&mut self);
 }
 
@@ -35,6 +36,15 @@ impl<'a> Mark for State<'a> {
         let _ = self.navigate_down(1);
     }
 
+    fn mark_next(&mut self, count: usize) {
+        let position_id = get_position(&self.positions_map, &self.current_dir);
+        for file in self.files[1].iter().skip(position_id).take(count) {
+            if !self.marked.iter().any(|f| f.name == file.name) {
+                self.marked.push(file.clone());
+            }
+        }
+    }
+
     fn clear_marks(&mut self) {
         self.marked.clear();
     }
@@ -62,6 +72,16 @@ mod tests {
         assert_eq!(state.marked.len(), initial_length - 1);
     }
 
+    #[test]
+    fn mark_next_marks_from_cursor() {
+        let mut state = create_test_state();
+        state.mark_next(2);
+        let names: Vec<&str> = state.marked.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["file1", "file2"]);
+        state.mark_next(5);
+        assert_eq!(state.marked.len(), 3);
+    }
+
     #[test]
     fn marks_and_moves_down() {
         let mut state = create_test_state();
@@ -1,19 +1,36 @@
 use std::collections::HashMap;
 use std::env;
 use std::io::{self};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use tui_textarea::TextArea;
 
 use crate::app::config::constants::model::NUM_COLUMNS;
+use crate::app::model::chooser::Chooser;
 use crate::app::model::clipboard::Clipboard;
+use crate::app::model::dual_pane::DualPane;
+use crate::app::model::file::get_current_file;
+use crate::app::model::find::FindListing;
+use crate::app::model::frecency::Frecency;
+use crate::app::model::jumps::JumpList;
+use crate::app::model::matcher::{MatchEngine, Matcher};
 use crate::app::model::miller::columns::MillerColumns;
 use crate::app::model::miller::entries::{DirEntry, FileEntry};
-use crate::app::model::miller::positions::parse_path_positions;
+use crate::app::model::miller::positions::{parse_path_positions, update_dir_position};
+use crate::app::model::miller::sort::Sort;
+use crate::app::model::miller::view::{DirViews, ViewOptions};
 use crate::app::model::notification::Notification;
+use crate::app::model::tabs::TabList;
+use crate::app::ui::file_preview::Highlighter;
 use crate::app::ui::modal::ModalKind;
 use crate::app::utils::config_parser::default_config::Config;
+use crate::app::utils::config_parser::keymap::{Keymap, KeymapMode, PendingKeys};
+use crate::app::utils::config_parser::opener::Openers;
+use crate::app::utils::config_parser::theme::Theme;
+use crate::app::utils::config_parser::{data_dir, state_dir};
 use crate::app::utils::i18n::Lang;
+use crate::app::utils::ls_colors::LsColors;
 pub mod file_managment;
 pub use file_managment::FileManager;
 pub mod bookmarks;
@@ -23,9 +40,42 @@ pub use navigation::Navigation;
 pub mod hint_bar;
 pub use hint_bar::HintBar;
 pub mod search;
-pub use search::Search;
+pub use search::{Search, SearchOrigin};
 pub mod mark;
 pub use mark::Mark;
+pub mod command;
+pub use command::CommandLine;
+pub mod config;
+pub use config::ConfigReload;
+pub mod open_with;
+pub use open_with::OpenWith;
+pub mod view;
+pub use view::LocalView;
+pub mod jumps;
+pub use jumps::Jumps;
+pub mod tabs;
+pub use tabs::Tabs;
+pub mod dual_pane;
+pub use dual_pane::DualPanes;
+pub mod goto;
+pub use goto::GoTo;
+pub mod finder;
+pub use finder::FuzzyFinder;
+pub mod frecency;
+pub use frecency::FrecencyJump;
+pub mod mouse;
+pub use mouse::Mouse;
+pub mod chooser;
+pub use chooser::Picker;
+pub mod filter;
+pub use filter::Filter;
+pub mod grep;
+pub use grep::ContentSearch;
+pub mod find;
+pub use find::Find;
+
+const VIEWS_FILE: &str = "views.toml";
+const FRECENCY_FILE: &str = "frecency.toml";
 
 #[derive(Debug, PartialEq)]
 pub enum Mode {
@@ -46,22 +96,65 @@ pub struct State<'a> {
     pub modal_type: ModalKind,
     pub input: TextArea<'a>,
     pub config: Config,
+    pub config_modified: Option<SystemTime>,
+    pub keymap: Keymap,
+    pub theme: Theme,
+    pub ls_colors: Option<LsColors>,
+    pub highlighter: Highlighter,
+    pub openers: Openers,
+    pub pending_keys: PendingKeys,
     pub from_external_app: bool,
     pub clipboard: Option<Clipboard>,
     pub notification: Option<Notification>,
     pub marked: Vec<FileEntry>,
-    pub search_pattern: Option<String>,
+    pub search_pattern: Option<Matcher>,
+    /// Set with `:set search=`; overrides the configured engine for this session only
+    pub search_engine: Option<MatchEngine>,
+    /// Where the search prompt was opened, restored when it is cancelled
+    pub search_origin: Option<SearchOrigin>,
     pub show_hidden_files: bool,
+    pub sort: Sort,
+    pub views: DirViews,
+    pub jumps: JumpList,
+    pub tabs: TabList,
+    pub dual_pane: Option<DualPane>,
+    pub frecency: Frecency,
+    /// Lines of the file preview scrolled past; reset whenever the columns change
+    pub preview_scroll: usize,
+    /// Set when started as a picker with `--choosefile` and friends
+    pub chooser: Option<Chooser>,
+    /// Find results listed in place of the entries of their root
+    pub find: Option<FindListing>,
 }
 
 impl<'a> State<'a> {
     pub fn new(config: Config) -> io::Result<Self> {
         let current_dir = env::current_dir()?;
+        let keymap = Keymap::from_config(&config.keys)?;
+        let theme = Theme::from_config(&config.theme)?;
+        let ls_colors = LsColors::from_config(&config.theme);
+        let highlighter = Highlighter::from_config(&config.preview, config.dir())?;
+        let openers = Openers::from_config(&config.openers)?;
+
+        let (views, views_error) = match state_dir().map(|dir| DirViews::load(dir.join(VIEWS_FILE)))
+        {
+            Some(Ok(views)) => (views, None),
+            Some(Err(e)) => (DirViews::default(), Some(e)),
+            None => (DirViews::default(), None),
+        };
+        let (frecency, frecency_error) =
+            match data_dir().map(|dir| Frecency::load(dir.join(FRECENCY_FILE))) {
+                Some(Ok(frecency)) => (frecency, None),
+                Some(Err(e)) => (Frecency::default(), Some(e)),
+                None => (Frecency::default(), None),
+            };
 
-        let miller_columns = MillerColumns::build_columns(&current_dir, 0, None, false)?;
+        let miller_columns =
+            MillerColumns::build_columns(&current_dir, 0, None, &views, &ViewOptions::default())?;
         let miller_positions = parse_path_positions(&current_dir, &miller_columns.files);
         let textarea = TextArea::default();
 
+        let tabs = TabList::new(&current_dir);
         Ok(State {
             current_dir,
             files: miller_columns.files,
@@ -70,26 +163,89 @@ impl<'a> State<'a> {
             mode: Mode::Normal,
             modal_type: ModalKind::Disabled,
             input: textarea,
+            config_modified: config::config_modified(&config),
             config,
+            keymap,
+            theme,
+            ls_colors,
+            highlighter,
+            openers,
+            pending_keys: PendingKeys::default(),
             from_external_app: false,
             show_hidden_files: false,
             clipboard: None,
-            notification: None,
+            notification: views_error
+                .map(|e| Lang::en_fmt("views_load_failed", &[&e.to_string()]))
+                .or_else(|| {
+                    frecency_error.map(|e| Lang::en_fmt("frecency_load_failed", &[&e.to_string()]))
+                })
+                .map(|msg| Notification::Error { msg: msg.into() }),
             marked: vec![],
             search_pattern: None,
+            search_engine: None,
+            search_origin: None,
+            sort: Sort::default(),
+            views,
+            jumps: JumpList::default(),
+            tabs,
+            dual_pane: None,
+            frecency,
+            preview_scroll: 0,
+            chooser: None,
+            find: None,
         })
     }
 
+    /// View settings for directories without overrides.
+    pub fn global_view(&self) -> ViewOptions {
+        ViewOptions {
+            show_hidden: self.show_hidden_files,
+            sort: self.sort,
+            filter: None,
+            session_filter: None,
+            preview: true,
+        }
+    }
+
+    pub fn view_of(&self, dir: &Path) -> ViewOptions {
+        self.views.resolve(dir, &self.global_view())
+    }
+
+    pub fn search_engine(&self) -> MatchEngine {
+        self.search_engine.unwrap_or(self.config.common.search)
+    }
+
     fn refresh_state(&mut self, new_pos_id: usize) -> io::Result<()> {
         self.hide_hint_bar();
-        let miller_columns = MillerColumns::build_columns(
-            &self.current_dir,
-            new_pos_id,
-            self.search_pattern.clone(),
-            self.show_hidden_files,
-        )?;
+        // the listing only stands in for its own root
+        if self
+            .find
+            .as_ref()
+            .is_some_and(|find| find.root != self.current_dir)
+        {
+            self.find = None;
+        }
+        let miller_columns = match &self.find {
+            Some(find) => MillerColumns::build_listing(
+                &self.current_dir,
+                &find.entries,
+                new_pos_id,
+                self.search_pattern.as_ref(),
+                &self.views,
+                &self.global_view(),
+            )?,
+            None => MillerColumns::build_columns(
+                &self.current_dir,
+                new_pos_id,
+                self.search_pattern.as_ref(),
+                &self.views,
+                &self.global_view(),
+            )?,
+        };
         self.files = miller_columns.files;
         self.dirs = miller_columns.dirs;
+        self.preview_scroll = 0;
+        self.refresh_other_pane();
         Ok(())
     }
 
@@ -126,6 +282,34 @@ impl<'a> State<'a> {
         Ok(())
     }
 
+    /// Like `reset_state_except_notifications`, after files were added, moved or
+    /// removed; an open find listing walks its tree again to follow them.
+    pub fn reset_state_after_changes(&mut self, new_pos_id: usize) -> io::Result<()> {
+        self.rescan_find();
+        self.refresh_state(new_pos_id)
+    }
+
+    pub fn rescan_find(&mut self) {
+        let show_hidden = self.view_of(&self.current_dir).show_hidden;
+        let respect_ignore = self.config.common.respect_ignore;
+        if let Some(find) = &mut self.find {
+            find.rescan(show_hidden, respect_ignore);
+        }
+    }
+
+    /// Rebuilds the columns after a view change, keeping the cursor on the same file.
+    pub fn refresh_keeping_selection(&mut self) {
+        let selected = get_current_file(&self.positions_map, &self.current_dir, &self.files[1])
+            .map(|file| file.name.clone());
+        let _ = self.reset_state_except_notifications(0);
+
+        let position_id = selected
+            .and_then(|name| self.files[1].iter().position(|f| f.name == name))
+            .unwrap_or(0);
+        update_dir_position(&mut self.positions_map, &self.current_dir, position_id);
+        let _ = self.reset_state_except_notifications(position_id);
+    }
+
     pub fn enter_normal_mode(&mut self) {
         self.mode = Mode::Normal;
         self.modal_type = ModalKind::Disabled;
@@ -150,6 +334,10 @@ impl<'a> State<'a> {
         .into();
     }
 
+    pub fn keymap_mode(&self) -> KeymapMode {
+        KeymapMode::from_state(&self.mode, &self.modal_type)
+    }
+
     fn setup_default_input(&mut self) {
         let textarea = TextArea::default();
         self.input = textarea;
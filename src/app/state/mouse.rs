@@ -0,0 +1,87 @@
+use std::io;
+
+use crate::app::{
+    model::miller::positions::update_dir_position,
+    state::{Navigation, State},
+};
+
+pub trait Mouse {
+    fn click_entry(&mut self, column: usize, index: usize, double: bool) -> io::Result<()>;
+    fn scroll_preview(&mut self, step: isize);
+}
+
+impl<'a> Mouse for State<'a> {
+    /// `column` counts the Miller columns from the left; a click in the parent
+    /// column goes up to it, one in the child column goes into it.
+    fn click_entry(&mut self, column: usize, index: usize, double: bool) -> io::Result<()> {
+        match column {
+            0 => {
+                if let Some(parent) = self.dirs[0].dir_name.clone() {
+                    update_dir_position(&mut self.positions_map, &parent, index);
+                    self.navigate_to_parent()?;
+                }
+            }
+            1 => self.navigate_to_position(index)?,
+            _ => {
+                if self.dirs[2].dir_name.is_some() {
+                    self.navigate_to_child()?;
+                    self.navigate_to_position(index)?;
+                }
+            }
+        }
+        if double {
+            self.navigate_to_child_or_exec()?;
+        }
+        Ok(())
+    }
+
+    fn scroll_preview(&mut self, step: isize) {
+        self.preview_scroll = self.preview_scroll.saturating_add_signed(step);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::app::{model::miller::positions::get_position, test_utils::create_test_state};
+
+    #[test]
+    fn clicks_select_across_columns() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("a/inner")).unwrap();
+        fs::create_dir(root.join("b")).unwrap();
+        fs::write(root.join("a/one"), "").unwrap();
+        fs::write(root.join("a/two"), "").unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root.join("b")).unwrap();
+
+        // parent column: a, b
+        state.click_entry(0, 0, false).unwrap();
+        assert_eq!(state.current_dir, root);
+        assert_eq!(get_position(&state.positions_map, &root), 0);
+
+        // child column of `a`: inner, one, two
+        state.click_entry(2, 2, false).unwrap();
+        assert_eq!(state.current_dir, root.join("a"));
+        assert_eq!(get_position(&state.positions_map, &state.current_dir), 2);
+
+        state.click_entry(1, 0, true).unwrap();
+        assert_eq!(state.current_dir, root.join("a/inner"));
+    }
+
+    #[test]
+    fn moving_resets_preview_scroll() {
+        let mut state = create_test_state();
+        state.scroll_preview(-3);
+        assert_eq!(state.preview_scroll, 0);
+        state.scroll_preview(5);
+        assert_eq!(state.preview_scroll, 5);
+        let root = tempfile::tempdir().unwrap();
+        state.change_dir(root.path().to_path_buf()).unwrap();
+        assert_eq!(state.preview_scroll, 0);
+    }
+}
@@ -1,4 +1,7 @@
-use std::io;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
 
 use crate::app::{
     model::{
@@ -9,6 +12,7 @@ use crate::app::{
         },
     },
     state::{FileManager, Mark, Mode, State},
+    utils::i18n::Lang,
 };
 
 pub trait Navigation {
@@ -17,6 +21,8 @@ pub trait Navigation {
     fn navigate_up(&mut self, step: usize) -> io::Result<()>;
     fn navigate_down(&mut self, step: usize) -> io::Result<()>;
     fn navigate_to_child_or_exec(&mut self) -> io::Result<()>;
+    fn navigate_to_position(&mut self, position_id: usize) -> io::Result<()>;
+    fn change_dir(&mut self, dir: PathBuf) -> io::Result<()>;
 }
 
 impl<'a> Navigation for State<'a> {
@@ -26,6 +32,7 @@ impl<'a> Navigation for State<'a> {
             let position_id = get_position(&self.positions_map, &self.current_dir);
             let _ = self.reset_state(position_id);
             update_parent_position(&mut self.positions_map, &self.current_dir, &self.files);
+            self.record_visit();
         }
         Ok(())
     }
@@ -35,6 +42,7 @@ impl<'a> Navigation for State<'a> {
             self.current_dir = child.to_path_buf();
             let position_id = get_position(&self.positions_map, &self.current_dir);
             let _ = self.reset_state(position_id);
+            self.record_visit();
         }
         Ok(())
     }
@@ -88,6 +96,61 @@ impl<'a> Navigation for State<'a> {
         }
         Ok(())
     }
+
+    fn navigate_to_position(&mut self, position_id: usize) -> io::Result<()> {
+        let current_position = get_position(&self.positions_map, &self.current_dir);
+        let target = position_id.min(self.files[1].len().saturating_sub(1));
+        if target < current_position {
+            self.navigate_up(current_position - target)
+        } else if target > current_position {
+            self.navigate_down(target - current_position)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn change_dir(&mut self, dir: PathBuf) -> io::Result<()> {
+        let previous = self.current_dir.clone();
+        self.enter_dir(dir)?;
+        if self.current_dir != previous {
+            self.jumps.record(&previous);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> State<'a> {
+    /// Switches to `dir` and restores its selected entry, without touching the jump list.
+    pub fn enter_dir(&mut self, dir: PathBuf) -> io::Result<()> {
+        if !dir.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                Lang::en_fmt("path_does_not_exist", &[&dir.to_string_lossy()]),
+            ));
+        }
+        self.current_dir = dir.canonicalize()?;
+        let position_id = get_position(&self.positions_map, &self.current_dir);
+        self.reset_state(position_id)?;
+        update_parent_position(&mut self.positions_map, &self.current_dir, &self.files);
+        self.record_visit();
+        Ok(())
+    }
+
+    /// Goes to the directory holding `path` with `path` selected.
+    pub fn select_path(&mut self, path: &Path) -> io::Result<()> {
+        let Some(parent) = path.parent() else {
+            return self.change_dir(path.to_path_buf());
+        };
+        self.change_dir(parent.to_path_buf())?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned());
+        let position_id = name
+            .and_then(|name| self.files[1].iter().position(|f| f.name == name))
+            .unwrap_or(0);
+        update_dir_position(&mut self.positions_map, &self.current_dir, position_id);
+        self.reset_state(position_id)
+    }
 }
 
 #[cfg(test)]
@@ -136,6 +199,26 @@ mod tests {
         assert_eq!(new_position, 0);
     }
 
+    #[test]
+    fn test_navigate_to_position() {
+        let mut state = create_test_state();
+        assert!(state.navigate_to_position(2).is_ok());
+        assert_eq!(get_position(&state.positions_map, &state.current_dir), 2);
+        assert!(state.navigate_to_position(0).is_ok());
+        assert_eq!(get_position(&state.positions_map, &state.current_dir), 0);
+        assert!(state.navigate_to_position(usize::MAX).is_ok());
+        assert_eq!(get_position(&state.positions_map, &state.current_dir), 2);
+    }
+
+    #[test]
+    fn test_change_dir() {
+        let mut state = create_test_state();
+        let dir = tempfile::tempdir().unwrap();
+        assert!(state.change_dir(dir.path().to_path_buf()).is_ok());
+        assert_eq!(state.current_dir, dir.path().canonicalize().unwrap());
+        assert!(state.change_dir(dir.path().join("missing")).is_err());
+    }
+
     #[test]
     fn test_navigate_down() {
         let mut state = create_test_state();
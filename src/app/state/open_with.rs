@@ -0,0 +1,198 @@
+use std::path::PathBuf;
+
+use crate::app::{
+    model::{
+        file::{build_full_path, get_current_file},
+        notification::Notification,
+    },
+    state::{Mark, Mode, State},
+    ui::modal::{BottomLineModalAction, ModalKind},
+    utils::{config_parser::opener::Opener, fs::open_with, i18n::Lang},
+};
+
+pub trait OpenWith {
+    fn open_with_menu(&mut self);
+    fn open_with_move(&mut self, step: isize);
+    fn commit_open_with(&mut self);
+    fn commit_open_with_command(&mut self);
+    fn close_open_with(&mut self);
+}
+
+impl<'a> OpenWith for State<'a> {
+    fn open_with_menu(&mut self) {
+        let files = self.selected_paths();
+        if files.is_empty() {
+            return;
+        }
+        let choices = self
+            .openers
+            .candidates(&files)
+            .into_iter()
+            .cloned()
+            .collect();
+        self.modal_type = ModalKind::OpenWith {
+            files,
+            choices,
+            selected: 0,
+        };
+    }
+
+    fn open_with_move(&mut self, step: isize) {
+        if let ModalKind::OpenWith {
+            choices, selected, ..
+        } = &mut self.modal_type
+        {
+            // the custom command entry sits after the rules
+            let last = choices.len();
+            *selected = selected.saturating_add_signed(step).min(last);
+        }
+    }
+
+    fn commit_open_with(&mut self) {
+        let ModalKind::OpenWith {
+            files,
+            choices,
+            selected,
+        } = std::mem::replace(&mut self.modal_type, ModalKind::Disabled)
+        else {
+            return;
+        };
+
+        match choices.get(selected) {
+            Some(opener) => self.launch(opener, &files),
+            None => {
+                self.mode = Mode::Insert;
+                self.setup_default_input();
+                self.modal_type = ModalKind::BottomLine {
+                    action: BottomLineModalAction::OpenWith { files },
+                };
+            }
+        }
+    }
+
+    fn commit_open_with_command(&mut self) {
+        let ModalKind::BottomLine {
+            action: BottomLineModalAction::OpenWith { files },
+        } = std::mem::replace(&mut self.modal_type, ModalKind::Disabled)
+        else {
+            return;
+        };
+        let line = self.input.lines().join("");
+        self.enter_normal_mode();
+
+        match Opener::from_command(&line) {
+            Ok(opener) => self.launch(&opener, &files),
+            Err(msg) => {
+                self.notification = Notification::Error { msg: msg.into() }.into();
+            }
+        }
+    }
+
+    fn close_open_with(&mut self) {
+        self.modal_type = ModalKind::Disabled;
+    }
+}
+
+impl<'a> State<'a> {
+    /// Marked files when there are any, otherwise the file under the cursor.
+    pub fn selected_paths(&self) -> Vec<PathBuf> {
+        if !self.marked.is_empty() {
+            return self
+                .marked
+                .iter()
+                .map(|file| build_full_path(&self.current_dir, file))
+                .collect();
+        }
+        get_current_file(&self.positions_map, &self.current_dir, &self.files[1])
+            .map(|file| vec![build_full_path(&self.current_dir, file)])
+            .unwrap_or_default()
+    }
+
+    fn launch(&mut self, opener: &Opener, files: &[PathBuf]) {
+        match open_with(opener, files, self.config.editor()) {
+            Ok(took_terminal) => {
+                self.from_external_app |= took_terminal;
+                self.clear_marks();
+                if matches!(self.mode, Mode::Visual { .. }) {
+                    self.enter_normal_mode();
+                }
+            }
+            Err(e) => {
+                self.notification = Notification::Error {
+                    msg: Lang::en_fmt("open_failed", &[&e.to_string()]).into(),
+                }
+                .into();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{
+        test_utils::create_test_state,
+        utils::config_parser::opener::{OpenerRule, Openers},
+    };
+
+    fn rule(command: &str) -> OpenerRule {
+        OpenerRule {
+            ext: vec![],
+            glob: None,
+            mime: None,
+            executable: None,
+            text: None,
+            command: command.into(),
+            terminal: true,
+            wait: false,
+        }
+    }
+
+    #[test]
+    fn menu_lists_rules_for_marked_files() {
+        let mut state = create_test_state();
+        state.openers = Openers::from_config(&[rule("less"), rule("jq .")]).unwrap();
+        state.mark_next(2);
+
+        state.open_with_menu();
+        let ModalKind::OpenWith { files, choices, .. } = &state.modal_type else {
+            panic!("picker not open");
+        };
+        assert_eq!(
+            files,
+            &[
+                PathBuf::from("/src/ui/tests/file1"),
+                PathBuf::from("/src/ui/tests/file2")
+            ]
+        );
+        assert_eq!(choices.len(), 2);
+    }
+
+    #[test]
+    fn custom_entry_opens_prompt() {
+        let mut state = create_test_state();
+        state.openers = Openers::from_config(&[rule("less")]).unwrap();
+        state.open_with_menu();
+
+        state.open_with_move(5);
+        assert!(matches!(
+            state.modal_type,
+            ModalKind::OpenWith { selected: 1, .. }
+        ));
+        state.open_with_move(-3);
+        assert!(matches!(
+            state.modal_type,
+            ModalKind::OpenWith { selected: 0, .. }
+        ));
+
+        state.open_with_move(1);
+        state.commit_open_with();
+        assert_eq!(state.mode, Mode::Insert);
+        assert!(matches!(
+            state.modal_type,
+            ModalKind::BottomLine {
+                action: BottomLineModalAction::OpenWith { .. }
+            }
+        ));
+    }
+}
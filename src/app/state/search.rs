@@ -1,71 +1,128 @@
+use std::mem;
+
 use crate::app::{
-    model::miller::{
-        entries::FileVariant,
-        positions::{get_position, update_dir_position},
+    model::{
+        matcher::Matcher,
+        miller::positions::{get_position, update_dir_position},
+        notification::Notification,
     },
     state::{Mode, State},
-    ui::modal::ModalKind,
+    ui::modal::{BottomLineModalAction, ModalKind},
     utils::i18n::Lang,
 };
 
+#[derive(Debug)]
+pub struct SearchOrigin {
+    position_id: usize,
+    pattern: Option<Matcher>,
+}
+
 pub trait Search {
     fn search(&mut self);
+    fn update_search_query(&mut self);
     fn commit_search(&mut self);
+    fn cancel_search(&mut self);
     fn next_match(&mut self, direction: String);
     fn exit_search_mode(&mut self);
 }
 
 impl<'a> Search for State<'a> {
     fn search(&mut self) {
+        self.search_origin = Some(SearchOrigin {
+            position_id: get_position(&self.positions_map, &self.current_dir),
+            pattern: self.search_pattern.clone(),
+        });
         self.mode = Mode::Insert;
-        self.modal_type = ModalKind::BottomLine;
+        self.modal_type = ModalKind::BottomLine {
+            action: BottomLineModalAction::Search,
+        };
+    }
+
+    /// Searches as the query is typed; a query that does not compile yet
+    /// (an unclosed regex group) shows no matches.
+    fn update_search_query(&mut self) {
+        if !matches!(
+            self.modal_type,
+            ModalKind::BottomLine {
+                action: BottomLineModalAction::Search
+            }
+        ) {
+            return;
+        }
+        let query = self.input.lines().join("");
+        let matcher = Matcher::new(&query, self.search_engine()).ok();
+        // refreshing the columns closes modals, the prompt has to stay up
+        let prompt = mem::replace(&mut self.modal_type, ModalKind::Disabled);
+        self.show_search(matcher.filter(|_| !query.is_empty()));
+        self.modal_type = prompt;
     }
 
     fn commit_search(&mut self) {
-        let query = self.input.lines().join("").to_lowercase();
+        let query = self.input.lines().join("");
 
-        self.search_pattern = Some(query);
-        let positiond_id = get_position(&self.positions_map, &self.current_dir);
-        self.setup_default_input();
-        self.mode = Mode::Search;
-        let _ = self.reset_state(positiond_id);
+        match Matcher::new(&query, self.search_engine()) {
+            Ok(matcher) => {
+                self.setup_default_input();
+                self.mode = Mode::Search;
+                self.show_search(Some(matcher));
+                self.search_origin = None;
+            }
+            Err(msg) => {
+                self.cancel_search();
+                self.notification = Notification::Error { msg: msg.into() }.into();
+            }
+        }
+    }
+
+    /// Puts the cursor and the previous search back as they were before `/`.
+    fn cancel_search(&mut self) {
+        self.enter_normal_mode();
+        let Some(origin) = self.search_origin.take() else {
+            return;
+        };
+        self.search_pattern = origin.pattern;
+        if self.search_pattern.is_some() {
+            self.mode = Mode::Search;
+        }
+        update_dir_position(
+            &mut self.positions_map,
+            &self.current_dir,
+            origin.position_id,
+        );
+        let _ = self.reset_state(origin.position_id);
     }
 
     fn next_match(&mut self, direction: String) {
-        if let Some(pattern) = &self.search_pattern {
-            let current_position = get_position(&self.positions_map, &self.current_dir);
-            let files = &self.files[1];
-            if files.is_empty() || pattern.is_empty() {
-                return;
-            }
+        let Some(matcher) = &self.search_pattern else {
+            return;
+        };
+        let current_position = get_position(&self.positions_map, &self.current_dir);
+        let forward = direction != "prev";
+        let order = self.match_order();
 
-            let start_index = match direction.as_ref() {
-                "next" => (current_position + 1) % files.len(),
-                "prev" => (current_position + files.len() - 1) % files.len(),
-                _ => (current_position + 1) % files.len(),
-            };
-
-            let mut found_index = None;
-
-            for i in 0..files.len() {
-                let index = (start_index + i) % files.len();
-                if match files[index].variant {
-                    FileVariant::Directory { is_matched, .. } => is_matched,
-                    FileVariant::File { is_matched, .. } => is_matched,
-                } {
-                    found_index = Some(index);
-                    break;
-                }
-            }
+        let found_index = match order.iter().position(|&id| id == current_position) {
+            Some(rank) if forward => order.get((rank + 1) % order.len()),
+            Some(rank) => order.get((rank + order.len() - 1) % order.len()),
+            None if matcher.engine().is_ranked() => order.first(),
+            // listing order: the nearest match past the cursor, wrapping around
+            None if forward => order
+                .iter()
+                .find(|&&id| id > current_position)
+                .or(order.first()),
+            None => order
+                .iter()
+                .rev()
+                .find(|&&id| id < current_position)
+                .or(order.last()),
+        };
 
-            if let Some(new_position) = found_index {
-                update_dir_position(&mut self.positions_map, &self.current_dir, new_position);
-                let _ = self.reset_state(new_position);
-            } else {
-                self.notification = Some(crate::app::state::Notification::Info {
-                    msg: Lang::en_fmt("no_matches", &[pattern]).into(),
-                });
-            }
+        if let Some(&new_position) = found_index {
+            update_dir_position(&mut self.positions_map, &self.current_dir, new_position);
+            let _ = self.reset_state(new_position);
+        } else {
+            self.notification = Some(Notification::Info {
+                msg: Lang::en_fmt("no_matches", &[matcher.query()]).into(),
+            });
         }
     }
 
@@ -76,3 +133,154 @@ impl<'a> Search for State<'a> {
         let _ = self.reset_state(positiond_id);
     }
 }
+
+impl<'a> State<'a> {
+    /// Highlights `matcher` and moves to its first match from where the prompt
+    /// was opened: the best one for ranked engines, otherwise the nearest one
+    /// at or below it.
+    fn show_search(&mut self, matcher: Option<Matcher>) {
+        let origin = self.search_origin.as_ref().map_or_else(
+            || get_position(&self.positions_map, &self.current_dir),
+            |origin| origin.position_id,
+        );
+        self.search_pattern = matcher;
+        let is_ranked = self
+            .search_pattern
+            .as_ref()
+            .is_some_and(|matcher| matcher.engine().is_ranked());
+
+        // the listing only changes its highlights, so it can be ranked before the refresh
+        let order = self.match_order();
+        let position_id = if is_ranked {
+            order.first()
+        } else {
+            order.iter().find(|&&id| id >= origin).or(order.first())
+        }
+        .copied()
+        .unwrap_or(origin);
+        update_dir_position(&mut self.positions_map, &self.current_dir, position_id);
+        let _ = self.reset_state(position_id);
+    }
+
+    /// Indexes of the matches in the current column, best first for ranked engines.
+    fn match_order(&self) -> Vec<usize> {
+        let Some(matcher) = &self.search_pattern else {
+            return vec![];
+        };
+        let mut matches: Vec<(usize, i64)> = self.files[1]
+            .iter()
+            .enumerate()
+            .filter_map(|(id, file)| matcher.find(&file.name).map(|m| (id, m.score)))
+            .collect();
+        if matcher.engine().is_ranked() {
+            matches.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        }
+        matches.into_iter().map(|(id, _)| id).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::app::{
+        model::matcher::MatchEngine, state::Navigation, test_utils::create_test_state,
+    };
+
+    fn search(state: &mut State, query: &str) {
+        state.search();
+        state.input.insert_str(query);
+        state.commit_search();
+    }
+
+    fn selected(state: &State) -> String {
+        let position = get_position(&state.positions_map, &state.current_dir);
+        state.files[1][position].name.clone()
+    }
+
+    #[test]
+    fn cycles_matches_in_listing_order() {
+        let root = tempfile::tempdir().unwrap();
+        for name in ["a_test.rs", "b.rs", "c_test.rs", "notes.txt"] {
+            fs::write(root.path().join(name), "").unwrap();
+        }
+        let mut state = create_test_state();
+        state.change_dir(root.path().to_path_buf()).unwrap();
+
+        search(&mut state, "test");
+        state.next_match("next".to_string());
+        assert_eq!(selected(&state), "c_test.rs");
+        state.next_match("next".to_string());
+        assert_eq!(selected(&state), "a_test.rs");
+        state.next_match("prev".to_string());
+        assert_eq!(selected(&state), "c_test.rs");
+
+        state.search_engine = Some(MatchEngine::Regex);
+        search(&mut state, r"\.txt$");
+        state.next_match("next".to_string());
+        assert_eq!(selected(&state), "notes.txt");
+    }
+
+    #[test]
+    fn fuzzy_starts_from_best_match() {
+        let root = tempfile::tempdir().unwrap();
+        for name in ["a_c_f_g", "cfg.toml"] {
+            fs::write(root.path().join(name), "").unwrap();
+        }
+        let mut state = create_test_state();
+        state.search_engine = Some(MatchEngine::Fuzzy);
+        state.change_dir(root.path().to_path_buf()).unwrap();
+
+        search(&mut state, "cfg");
+        assert_eq!(selected(&state), "cfg.toml");
+    }
+
+    #[test]
+    fn invalid_pattern_is_reported() {
+        let mut state = create_test_state();
+        state.search_engine = Some(MatchEngine::Regex);
+        search(&mut state, "(");
+        assert_eq!(state.mode, Mode::Normal);
+        assert!(state.search_pattern.is_none());
+        assert!(matches!(
+            state.notification,
+            Some(Notification::Error { .. })
+        ));
+    }
+
+    #[test]
+    fn typing_jumps_and_cancel_restores() {
+        let root = tempfile::tempdir().unwrap();
+        for name in ["alpha", "beta", "gamma", "delta_2"] {
+            fs::write(root.path().join(name), "").unwrap();
+        }
+        let mut state = create_test_state();
+        state.change_dir(root.path().to_path_buf()).unwrap();
+        // alpha, beta, delta_2, gamma
+        state.navigate_down(1).unwrap();
+
+        state.search();
+        for c in ['a', 'm'] {
+            state.input.insert_char(c);
+            state.update_search_query();
+        }
+        assert_eq!(selected(&state), "gamma");
+        assert!(state.modal_type.is_bottom_line());
+        state.input.delete_char();
+        state.update_search_query();
+        // nearest match at or below where `/` was pressed
+        assert_eq!(selected(&state), "beta");
+        assert_eq!(count_matched(&state), 4);
+
+        state.cancel_search();
+        assert_eq!(selected(&state), "beta");
+        assert_eq!(state.mode, Mode::Normal);
+        assert!(state.search_pattern.is_none());
+        assert_eq!(count_matched(&state), 0);
+    }
+
+    fn count_matched(state: &State) -> usize {
+        crate::app::model::file::count_matched_files(&state.files[1])
+    }
+}
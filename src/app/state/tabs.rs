@@ -0,0 +1,194 @@
+use std::{io, mem, path::PathBuf};
+
+use crate::app::{
+    model::{miller::positions::get_position, notification::Notification, tabs::Tab},
+    state::{Mode, Navigation, State},
+    utils::i18n::Lang,
+};
+
+pub trait Tabs {
+    fn new_tab(&mut self, dir: Option<PathBuf>) -> io::Result<()>;
+    fn close_tab(&mut self);
+    fn switch_tab(&mut self, index: usize);
+    fn next_tab(&mut self, step: isize);
+    fn move_tab(&mut self, step: isize);
+}
+
+impl<'a> Tabs for State<'a> {
+    /// Opens a tab next to the active one, starting where the active tab is.
+    fn new_tab(&mut self, dir: Option<PathBuf>) -> io::Result<()> {
+        if let Some(dir) = &dir {
+            if !dir.is_dir() {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    Lang::en_fmt("path_does_not_exist", &[&dir.to_string_lossy()]),
+                ));
+            }
+        }
+        let tab = Tab {
+            current_dir: self.current_dir.clone(),
+            positions_map: self.positions_map.clone(),
+            ..Tab::default()
+        };
+        self.store_tab();
+        self.tabs.active += 1;
+        self.tabs.list.insert(self.tabs.active, tab);
+        self.load_tab();
+
+        match dir {
+            Some(dir) => self.change_dir(dir),
+            None => Ok(()),
+        }
+    }
+
+    fn close_tab(&mut self) {
+        if self.tabs.len() == 1 {
+            self.notification = Notification::Warn {
+                msg: Lang::en("last_tab").into(),
+            }
+            .into();
+            return;
+        }
+        self.tabs.list.remove(self.tabs.active);
+        self.tabs.active = self.tabs.active.min(self.tabs.len() - 1);
+        self.load_tab();
+    }
+
+    fn switch_tab(&mut self, index: usize) {
+        if index == self.tabs.active || index >= self.tabs.len() {
+            return;
+        }
+        self.store_tab();
+        self.tabs.active = index;
+        self.load_tab();
+    }
+
+    fn next_tab(&mut self, step: isize) {
+        let index = self.tabs.offset(step);
+        self.switch_tab(index);
+    }
+
+    fn move_tab(&mut self, step: isize) {
+        self.tabs.move_active(step);
+    }
+}
+
+impl<'a> State<'a> {
+    /// Moves the navigation state on screen out of `State`.
+    pub fn take_tab(&mut self) -> Tab {
+        Tab {
+            current_dir: self.current_dir.clone(),
+            positions_map: mem::take(&mut self.positions_map),
+            marked: mem::take(&mut self.marked),
+            search_pattern: self.search_pattern.take(),
+            jumps: mem::take(&mut self.jumps),
+        }
+    }
+
+    /// Puts `tab` on screen and rebuilds the columns for it.
+    pub fn put_tab(&mut self, tab: Tab) {
+        self.current_dir = tab.current_dir;
+        self.positions_map = tab.positions_map;
+        self.marked = tab.marked;
+        self.search_pattern = tab.search_pattern;
+        self.jumps = tab.jumps;
+
+        self.enter_normal_mode();
+        if self.search_pattern.is_some() {
+            self.mode = Mode::Search;
+        }
+        let position_id = get_position(&self.positions_map, &self.current_dir);
+        if let Err(e) = self.reset_state(position_id) {
+            self.notification = Notification::Error {
+                msg: e.to_string().into(),
+            }
+            .into();
+        }
+    }
+
+    fn store_tab(&mut self) {
+        self.tabs.list[self.tabs.active] = self.take_tab();
+    }
+
+    fn load_tab(&mut self) {
+        let tab = mem::take(&mut self.tabs.list[self.tabs.active]);
+        // the bar still needs the directory name of the tab on screen
+        self.tabs.list[self.tabs.active].current_dir = tab.current_dir.clone();
+        self.put_tab(tab);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::app::{
+        model::matcher::{MatchEngine, Matcher},
+        state::Mark,
+        test_utils::create_test_state,
+    };
+
+    #[test]
+    fn tabs_keep_their_own_dir_and_marks() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        fs::create_dir(root.join("build")).unwrap();
+        fs::write(root.join("file"), "").unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root.clone()).unwrap();
+        state.mark_next(1);
+
+        state.new_tab(Some(root.join("build"))).unwrap();
+        assert_eq!(state.tabs.active, 1);
+        assert_eq!(state.current_dir, root.join("build"));
+        assert!(state.marked.is_empty());
+
+        state.next_tab(1);
+        assert_eq!(state.tabs.active, 0);
+        assert_eq!(state.current_dir, root);
+        assert_eq!(state.marked.len(), 1);
+
+        state.close_tab();
+        assert_eq!(state.tabs.len(), 1);
+        assert_eq!(state.current_dir, root.join("build"));
+        state.close_tab();
+        assert!(matches!(
+            state.notification,
+            Some(Notification::Warn { .. })
+        ));
+    }
+
+    #[test]
+    fn new_tab_inherits_current_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root.clone()).unwrap();
+        state.new_tab(None).unwrap();
+        assert_eq!(state.current_dir, root);
+        assert_eq!(state.tabs.titles(&state.current_dir).len(), 2);
+        assert!(state.new_tab(Some(root.join("missing"))).is_err());
+        assert_eq!(state.tabs.len(), 2);
+    }
+
+    #[test]
+    fn tab_keeps_its_search() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+
+        let mut state = create_test_state();
+        state.change_dir(root).unwrap();
+        state.search_pattern = Some(Matcher::new("file", MatchEngine::Substring).unwrap());
+        state.mode = Mode::Search;
+
+        state.new_tab(None).unwrap();
+        assert_eq!(state.mode, Mode::Normal);
+        assert!(state.search_pattern.is_none());
+        state.next_tab(-1);
+        assert_eq!(state.mode, Mode::Search);
+        assert!(state.search_pattern.is_some());
+    }
+}
@@ -0,0 +1,56 @@
+use crate::app::{
+    model::{miller::view::DirView, notification::Notification},
+    state::State,
+    utils::i18n::Lang,
+};
+
+pub trait LocalView {
+    fn set_local_view(&mut self, update: impl FnOnce(&mut DirView));
+}
+
+impl<'a> LocalView for State<'a> {
+    /// Changes the overrides of the current directory and persists them.
+    fn set_local_view(&mut self, update: impl FnOnce(&mut DirView)) {
+        if let Err(e) = self.views.update(&self.current_dir, update) {
+            self.notification = Notification::Warn {
+                msg: Lang::en_fmt("views_save_failed", &[&e.to_string()]).into(),
+            }
+            .into();
+        }
+        self.refresh_keeping_selection();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::app::{model::miller::sort::SortBy, test_utils::create_test_state};
+
+    #[test]
+    fn overrides_apply_to_their_dir_only() {
+        let root = tempfile::tempdir().unwrap();
+        let dotfiles = root.path().join("dotfiles");
+        fs::create_dir(&dotfiles).unwrap();
+        fs::write(dotfiles.join(".zshrc"), "").unwrap();
+        fs::write(dotfiles.join("README"), "").unwrap();
+        fs::write(root.path().join(".hidden"), "").unwrap();
+
+        let mut state = create_test_state();
+        state.current_dir = dotfiles.clone();
+        state.set_local_view(|view| {
+            view.hidden = Some(true);
+            view.sort = Some(SortBy::Mtime);
+        });
+
+        let names = |column: usize| -> Vec<String> {
+            state.files[column].iter().map(|f| f.name.clone()).collect()
+        };
+        assert!(names(1).contains(&".zshrc".to_string()));
+        // the parent column keeps the global view
+        assert_eq!(names(0), ["dotfiles"]);
+        assert_eq!(state.view_of(&dotfiles).sort.by, SortBy::Mtime);
+        assert!(!state.show_hidden_files);
+    }
+}
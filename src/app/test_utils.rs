@@ -2,10 +2,24 @@ use std::{collections::HashMap, path::PathBuf};
 use tui_textarea::TextArea;
 
 use crate::app::{
-    model::miller::entries::{DirEntry, FileEntry, FileVariant},
+    model::{
+        frecency::Frecency,
+        jumps::JumpList,
+        miller::{
+            entries::{DirEntry, FileEntry, FileKind, FileVariant},
+            sort::Sort,
+            view::DirViews,
+        },
+        tabs::TabList,
+    },
     state::{Mode, State},
-    ui::modal::ModalKind,
-    utils::config_parser::default_config::Config,
+    ui::{file_preview::Highlighter, modal::ModalKind},
+    utils::config_parser::{
+        default_config::Config,
+        keymap::{Keymap, PendingKeys},
+        opener::Openers,
+        theme::Theme,
+    },
 };
 
 pub fn create_test_state() -> State<'static> {
@@ -14,6 +28,7 @@ pub fn create_test_state() -> State<'static> {
 
     positions_map.insert(current_dir.clone(), 0);
 
+    let tabs = TabList::new(&current_dir);
     State {
         current_dir,
         files: [
@@ -27,6 +42,8 @@ pub fn create_test_state() -> State<'static> {
                         last_modified: Some("2023-10-01 12:00".into()),
                         is_matched: false,
                     },
+                    kind: FileKind::Regular,
+                    path: None,
                 },
                 FileEntry {
                     name: "file2".into(),
@@ -36,6 +53,8 @@ pub fn create_test_state() -> State<'static> {
                         last_modified: Some("2023-10-01 12:00".into()),
                         is_matched: false,
                     },
+                    kind: FileKind::Regular,
+                    path: None,
                 },
                 FileEntry {
                     name: "file3".into(),
@@ -45,6 +64,8 @@ pub fn create_test_state() -> State<'static> {
                         last_modified: Some("2023-10-01 12:00".into()),
                         is_matched: false,
                     },
+                    kind: FileKind::Regular,
+                    path: None,
                 },
             ],
             vec![],
@@ -68,11 +89,29 @@ pub fn create_test_state() -> State<'static> {
         positions_map,
         input: TextArea::default(),
         config: Config::default(),
+        config_modified: None,
+        keymap: Keymap::from_config(&Default::default()).unwrap(),
+        theme: Theme::default(),
+        ls_colors: None,
+        highlighter: Highlighter::default(),
+        openers: Openers::default(),
+        pending_keys: PendingKeys::default(),
         from_external_app: false,
         clipboard: None,
         notification: None,
         marked: vec![],
         search_pattern: None,
+        search_engine: None,
+        search_origin: None,
         show_hidden_files: false,
+        sort: Sort::default(),
+        views: DirViews::default(),
+        jumps: JumpList::default(),
+        tabs,
+        dual_pane: None,
+        frecency: Frecency::default(),
+        preview_scroll: 0,
+        chooser: None,
+        find: None,
     }
 }
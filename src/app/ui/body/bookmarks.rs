@@ -47,7 +47,9 @@ impl Bookmarks {
             .skip(offset)
             .take(visible_height)
             .enumerate()
-            .map(|(row_id, file)| Row::bookmarks_build(row_id, file.to_string(), true, cursor))
+            .map(|(row_id, file)| {
+                Row::bookmarks_build(state, row_id, file.to_string(), true, cursor)
+            })
             .collect();
 
         let path_strings: Vec<String> = paths
@@ -60,7 +62,9 @@ impl Bookmarks {
             .skip(offset)
             .take(visible_height)
             .enumerate()
-            .map(|(row_id, path)| Row::bookmarks_build(row_id, path.to_string(), false, cursor))
+            .map(|(row_id, path)| {
+                Row::bookmarks_build(state, row_id, path.to_string(), false, cursor)
+            })
             .collect();
 
         ColumnsWidget::new(
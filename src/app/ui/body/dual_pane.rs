@@ -0,0 +1,119 @@
+use std::{path::Path, rc::Rc};
+
+use ratatui::{
+    layout::{Constraint, Direction, Flex, Layout},
+    prelude::Rect,
+    widgets::{Block, List, ListItem, Paragraph, Widget},
+};
+
+use crate::app::{
+    model::{
+        dual_pane::DualPane,
+        miller::{entries::FileEntry, positions::get_position},
+    },
+    state::State,
+    ui::body::{
+        components::column_widget::{ColumnWidget, ColumnsWidget},
+        Row,
+    },
+};
+
+pub struct DualPaneView;
+
+impl DualPaneView {
+    pub fn build<'a>(state: &'a State, dual: &'a DualPane, area: Rect) -> impl Widget + 'a {
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+
+        let focused = (
+            state.current_dir.as_path(),
+            state.files[1].as_slice(),
+            get_position(&state.positions_map, &state.current_dir),
+            true,
+        );
+        let other = (
+            dual.other.current_dir.as_path(),
+            dual.other_files.as_slice(),
+            get_position(&dual.other.positions_map, &dual.other.current_dir),
+            false,
+        );
+        let panes = if dual.focus_left {
+            [focused, other]
+        } else {
+            [other, focused]
+        };
+
+        let widgets = panes
+            .into_iter()
+            .zip(layout.iter())
+            .map(|((dir, files, cursor, is_focused), area)| {
+                Self::pane(state, dir, files, cursor, is_focused, *area)
+            })
+            .collect();
+
+        ColumnsWidget::new(widgets, layout)
+    }
+
+    fn pane<'a>(
+        state: &'a State,
+        dir: &Path,
+        files: &'a [FileEntry],
+        cursor: usize,
+        is_focused: bool,
+        area: Rect,
+    ) -> ColumnWidget<'a> {
+        let border_style = if is_focused {
+            state.theme.modal_border
+        } else {
+            state.theme.tab
+        };
+        let block = Block::bordered()
+            .title(format!(" {} ", dir.display()))
+            .border_style(border_style);
+
+        if files.is_empty() {
+            return ColumnWidget::Paragraph(Paragraph::new("Empty directory").block(block));
+        }
+
+        let col_width = area.width.saturating_sub(2) as usize;
+        let visible_height = area.height.saturating_sub(2) as usize;
+        // keep the cursor on screen, scrolling only once it reaches the bottom
+        let offset = (cursor + 1).saturating_sub(visible_height);
+
+        let row_layout = Rc::new(
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Length(1),
+                    Constraint::Percentage(76),
+                    Constraint::Percentage(12),
+                    Constraint::Percentage(12),
+                    Constraint::Length(1),
+                ])
+                .flex(Flex::SpaceBetween)
+                .split(Rect::new(0, 0, col_width as u16, 1)),
+        );
+
+        let list_items: Vec<ListItem> = files
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible_height)
+            .map(|(row_id, file)| {
+                Row::build(
+                    state,
+                    Rc::clone(&row_layout),
+                    row_id,
+                    file,
+                    is_focused,
+                    cursor,
+                    col_width,
+                )
+            })
+            .collect();
+
+        ColumnWidget::List(List::new(list_items).block(block))
+    }
+}
@@ -1,40 +1,40 @@
 use std::rc::Rc;
 
 use ratatui::{
-    layout::{Constraint, Direction, Flex, Layout},
+    layout::{Constraint, Direction, Flex, Layout, Position},
     prelude::Rect,
     text::Line,
     widgets::{Block, List, ListItem, Paragraph, Widget},
 };
 
 use crate::app::{
-    config::constants::ui::{COLUMN_PERCENTAGE, FIRST_COLUMN_PERCENTAGE},
+    config::constants::{
+        model::NUM_COLUMNS,
+        ui::{COLUMN_PERCENTAGE, FIRST_COLUMN_PERCENTAGE},
+    },
     model::{
         file::{build_full_path, get_current_file},
         miller::{entries::FileVariant, positions::get_position},
     },
     state::State,
-    ui::{
-        body::components::column_widget::{ColumnWidget, ColumnsWidget},
-        file_preview::highlight_file,
-    },
+    ui::body::components::column_widget::{ColumnWidget, ColumnsWidget},
 };
 
 pub mod bookmarks;
 pub mod components;
+pub mod dual_pane;
 pub mod row;
 pub use row::Row;
 
+/// Preview bytes read on top of the base size per line scrolled past
+const PREVIEW_BYTES_PER_LINE: usize = 128;
+
 pub struct Body;
 
 impl Body {
-    pub fn build<'a>(state: &'a State, area: Rect) -> impl Widget + 'a {
-        let position_id = get_position(&state.positions_map, &state.current_dir);
-        let constraints: Vec<Constraint> = state
-            .files
-            .iter()
-            .enumerate()
-            .map(|(i, _)| {
+    pub fn layout(area: Rect) -> Rc<[Rect]> {
+        let constraints: Vec<Constraint> = (0..NUM_COLUMNS)
+            .map(|i| {
                 if i == 0 {
                     Constraint::Percentage(FIRST_COLUMN_PERCENTAGE)
                 } else {
@@ -43,10 +43,57 @@ impl Body {
             })
             .collect();
 
-        let layout = Layout::default()
+        Layout::default()
             .direction(Direction::Horizontal)
             .constraints(constraints)
-            .split(area);
+            .split(area)
+    }
+
+    /// Column and entry index drawn at `position`, as laid out by `build`.
+    pub fn entry_at(state: &State, area: Rect, position: Position) -> Option<(usize, usize)> {
+        let layout = Self::layout(area);
+        let col_id = layout.iter().position(|rect| rect.contains(position))?;
+        let files = &state.files[col_id];
+        let visible_height = layout[col_id].height.saturating_sub(2) as usize;
+        let offset = Self::offset(
+            col_id,
+            files.len(),
+            Self::cursor(state, col_id),
+            visible_height,
+        );
+        let index = (position.y - layout[col_id].y) as usize + offset;
+        (index < files.len()).then_some((col_id, index))
+    }
+
+    fn cursor(state: &State, col_id: usize) -> usize {
+        if col_id == 1 {
+            get_position(&state.positions_map, &state.current_dir)
+        } else {
+            state.dirs[col_id]
+                .dir_name
+                .as_ref()
+                .map_or(0, |name| get_position(&state.positions_map, name))
+        }
+    }
+
+    /// First row shown in a column, keeping the cursor clear of the bottom edge.
+    fn offset(col_id: usize, len: usize, cursor: usize, visible_height: usize) -> usize {
+        const TARGET_POSITION_DOWN: usize = 6;
+
+        if col_id == 0 || len <= visible_height {
+            return 0;
+        }
+        let max_possible_offset = len.saturating_sub(visible_height);
+        let upper_bound = visible_height.saturating_sub(TARGET_POSITION_DOWN); // 30 - 6 = 24;
+        if cursor >= upper_bound {
+            (cursor - upper_bound).min(max_possible_offset)
+        } else {
+            0
+        }
+    }
+
+    pub fn build<'a>(state: &'a State, area: Rect) -> impl Widget + 'a {
+        let layout = Self::layout(area);
 
         let widgets: Vec<ColumnWidget<'a>> = state
             .files
@@ -59,30 +106,8 @@ impl Body {
                 let is_current_or_child_column = is_current_column || is_child_column;
                 let visible_height = layout[col_id].height.saturating_sub(2) as usize;
 
-                const TARGET_POSITION_DOWN: usize = 6;
-
-                let cursor = if is_current_column {
-                    position_id
-                } else if (is_child_column && col_id < state.dirs.len()) || is_parent_column {
-                    state.dirs[col_id]
-                        .dir_name
-                        .as_ref()
-                        .map_or(0, |name| get_position(&state.positions_map, name))
-                } else {
-                    0
-                };
-
-                let offset = if is_current_or_child_column && dir.len() > visible_height {
-                    let max_possible_offset = dir.len().saturating_sub(visible_height);
-                    let upper_bound = visible_height - TARGET_POSITION_DOWN; // 30 - 6 = 24;
-                    if cursor >= upper_bound {
-                        (cursor - upper_bound).min(max_possible_offset)
-                    } else {
-                        0
-                    }
-                } else {
-                    0
-                };
+                let cursor = Self::cursor(state, col_id);
+                let offset = Self::offset(col_id, dir.len(), cursor, visible_height);
 
                 let col_width = layout[col_id].width as usize;
 
@@ -109,11 +134,15 @@ impl Body {
                     let is_current_column_and_selected_file =
                         current_file.is_some_and(|e| matches!(e.variant, FileVariant::File { .. }));
 
-                    let preview = if is_current_column_and_selected_file {
-                        let bytes_size = 2048;
+                    let preview = if !state.view_of(&state.current_dir).preview {
+                        vec![Line::from("Preview off")]
+                    } else if is_current_column_and_selected_file {
+                        let bytes_size = 2048 + state.preview_scroll * PREVIEW_BYTES_PER_LINE;
                         if let Some(file) = current_file {
                             let filepath = build_full_path(&state.current_dir, file);
-                            highlight_file(filepath.to_str().unwrap_or(""), bytes_size)
+                            state
+                                .highlighter
+                                .highlight_file(&filepath, bytes_size, state.preview_scroll)
                                 .unwrap_or(vec![Line::from("Error reading file")])
                         } else {
                             vec![Line::from("Empty")]
@@ -136,14 +165,13 @@ impl Body {
                         .enumerate()
                         .map(|(row_id, file)| {
                             Row::build(
+                                state,
                                 Rc::clone(&row_layout),
                                 row_id + offset,
                                 file,
                                 is_current_column,
                                 cursor,
                                 col_width,
-                                &state.marked,
-                                &state.mode,
                             )
                         })
                         .collect();
@@ -155,3 +183,27 @@ impl Body {
         ColumnsWidget::new(widgets, layout)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::test_utils::create_test_state;
+
+    #[test]
+    fn maps_clicks_to_entries() {
+        let state = create_test_state();
+        let area = Rect::new(0, 1, 100, 20);
+        let current = Body::layout(area)[1];
+
+        assert_eq!(
+            Body::entry_at(&state, area, Position::new(current.x, 2)),
+            Some((1, 1))
+        );
+        // below the last entry or in the empty parent column
+        assert_eq!(
+            Body::entry_at(&state, area, Position::new(current.x, 15)),
+            None
+        );
+        assert_eq!(Body::entry_at(&state, area, Position::new(0, 1)), None);
+    }
+}
@@ -1,16 +1,16 @@
-use std::rc::Rc;
+use std::{ops::Range, rc::Rc};
 
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Style, Stylize},
+    style::Style,
     text::{Line, Span},
     widgets::{ListItem, Widget},
 };
 
 use crate::app::{
     model::miller::entries::{FileEntry, FileVariant},
-    state::Mode,
+    state::{Mode, State},
     utils::format_bytes,
 };
 
@@ -18,15 +18,15 @@ pub struct Row {}
 
 impl Row {
     pub fn build<'a>(
+        state: &'a State,
         row_layout: Rc<[Rect]>,
         row_id: usize,
         file: &'a FileEntry,
         is_current_column: bool,
         position_id: usize,
         col_width: usize,
-        marked: &'a [FileEntry],
-        mode: &'a Mode,
     ) -> ListItem<'a> {
+        let theme = &state.theme;
         let meta = match file.variant {
             FileVariant::Directory { len, .. } => len.map(|e| e.to_string()).unwrap_or_default(),
             FileVariant::File { size, .. } => size.map(format_bytes).unwrap_or_default(),
@@ -35,39 +35,19 @@ impl Row {
         let name = file.name.as_str();
 
         let is_selected_column = row_id == position_id;
-        let is_marked = is_current_column && marked.iter().any(|f| f.name == file.name);
+        let is_marked = is_current_column && state.marked.iter().any(|f| f.name == file.name);
 
         let mut style = match file.variant {
-            FileVariant::Directory { is_matched, .. } => {
-                if is_selected_column {
-                    Style::default()
-                        .bg(Color::Blue)
-                        .fg(Color::Rgb(0, 0, 0))
-                        .bold()
-                } else if is_matched {
-                    Style::default().fg(Color::Red).bold()
-                } else {
-                    Style::default().fg(Color::Blue).bold()
-                }
-            }
-            FileVariant::File { is_matched, .. } => {
-                if is_selected_column {
-                    Style::default()
-                        .bg(Color::White)
-                        .fg(Color::Rgb(0, 0, 0))
-                        .bold()
-                } else if is_matched {
-                    Style::default().fg(Color::Red).bold()
-                } else {
-                    Style::default().fg(Color::White).bold()
-                }
-            }
+            FileVariant::Directory { .. } if is_selected_column => theme.selected_dir,
+            FileVariant::Directory { .. } => Self::ls_style(state, file, theme.dir),
+            FileVariant::File { .. } if is_selected_column => theme.selected_file,
+            FileVariant::File { .. } => Self::ls_style(state, file, theme.file),
         };
 
-        if (matches!(mode, Mode::Visual { .. }) || is_marked) && is_selected_column {
-            style = style.bg(Color::Yellow).fg(Color::Rgb(0, 0, 0));
+        if (matches!(state.mode, Mode::Visual { .. }) || is_marked) && is_selected_column {
+            style = style.patch(theme.marked_selected);
         } else if is_marked {
-            style = style.fg(Color::Yellow);
+            style = style.patch(theme.marked);
         }
 
         let padded_meta = if meta.len() >= meta_width {
@@ -82,7 +62,14 @@ impl Row {
             cell.set_symbol(" ");
             cell.set_style(style);
         }
-        Span::styled(name, style).render(row_layout[1], &mut buffer);
+        let ranges = state
+            .search_pattern
+            .as_ref()
+            .and_then(|matcher| matcher.find(name))
+            .map(|m| m.ranges)
+            .unwrap_or_default();
+        Self::highlight(name, &ranges, style, style.patch(theme.matched))
+            .render(row_layout[1], &mut buffer);
         Span::styled(padded_meta, style).render(row_layout[3], &mut buffer);
 
         let line = Line::from(
@@ -96,20 +83,49 @@ impl Row {
         ListItem::new(line).style(style)
     }
 
+    /// `name` split so the byte `ranges` get `matched_style`.
+    fn highlight<'n>(
+        name: &'n str,
+        ranges: &[Range<usize>],
+        style: Style,
+        matched_style: Style,
+    ) -> Line<'n> {
+        let mut spans = Vec::with_capacity(ranges.len() * 2 + 1);
+        let mut end = 0;
+        for range in ranges {
+            if range.start > end {
+                spans.push(Span::styled(&name[end..range.start], style));
+            }
+            spans.push(Span::styled(&name[range.clone()], matched_style));
+            end = range.end;
+        }
+        if end < name.len() {
+            spans.push(Span::styled(&name[end..], style));
+        }
+        Line::from(spans)
+    }
+
+    /// `LS_COLORS` overrides whatever parts of the theme style it sets.
+    fn ls_style(state: &State, file: &FileEntry, base: Style) -> Style {
+        state
+            .ls_colors
+            .as_ref()
+            .and_then(|colors| colors.style_for(&file.name, file.kind))
+            .map_or(base, |style| base.patch(style))
+    }
+
     pub fn bookmarks_build<'a>(
+        state: &State,
         row_id: usize,
         file: String,
         is_current_column: bool,
         position_id: usize,
     ) -> ListItem<'a> {
         let is_selected_column = is_current_column && row_id == position_id;
-        let style = if is_selected_column {
-            Style::default()
-                .bg(Color::LightCyan)
-                .fg(Color::Rgb(0, 0, 0))
-                .bold()
+        let style: Style = if is_selected_column {
+            state.theme.bookmark_selected
         } else {
-            Style::default().fg(Color::Gray).bold()
+            state.theme.bookmark
         };
 
         let line = Line::from(Span::styled(file, style));
@@ -1,67 +1,268 @@
 use std::{
     fs::File,
     io::{self, Read},
+    path::Path,
 };
 
+use glob::Pattern;
+use once_cell::sync::Lazy;
 use ratatui::{
     style::{Color, Style},
     text::{Line, Span},
 };
 use syntect::{
     easy::HighlightLines,
-    highlighting::{Style as SyntectStyle, ThemeSet},
-    parsing::SyntaxSet,
+    highlighting::{Style as SyntectStyle, Theme, ThemeSet},
+    parsing::{SyntaxReference, SyntaxSet},
 };
 
-pub fn highlight_file(file_path: &str, max_bytes: usize) -> io::Result<Vec<Line<'static>>> {
-    if is_binary_file(file_path)? {
-        return Ok(vec![Line::from("Binary or unsupported file")]);
-    }
-    let file = File::open(file_path)?;
-    let mut content = String::new();
-    file.take(max_bytes as u64).read_to_string(&mut content)?;
-
-    content = content.replace('\t', "        ");
-
-    let ps = SyntaxSet::load_defaults_newlines();
-    let ts = ThemeSet::load_defaults();
-
-    let syntax = ps
-        .find_syntax_for_file(file_path)
-        .unwrap_or_else(|_| Some(ps.find_syntax_plain_text()))
-        .unwrap_or(ps.find_syntax_plain_text());
-
-    let mut h = HighlightLines::new(syntax, &ts.themes["base16-ocean.dark"]);
-
-    let mut lines = Vec::new();
-    for line in content.lines().take(50) {
-        let ranges: Vec<(SyntectStyle, &str)> = h
-            .highlight_line(line, &ps)
-            .unwrap_or_else(|_| vec![(SyntectStyle::default(), line)]);
-        let spans: Vec<Span> = ranges
-            .into_iter()
-            .map(|(style, text)| {
-                Span::styled(
-                    text.to_string(),
-                    Style::default().fg(Color::Rgb(
-                        style.foreground.r,
-                        style.foreground.g,
-                        style.foreground.b,
-                    )),
-                )
+use crate::app::utils::{
+    config_parser::default_config::PreviewConfig,
+    fs::{expand_path, is_binary_file},
+};
+
+// decoding the bundled dumps is slow, do it at most once per process
+static DEFAULT_SYNTAXES: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+static DEFAULT_THEMES: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+/// Syntaxes and theme for the file preview, loaded once per config (re)load.
+#[derive(Debug)]
+pub struct Highlighter {
+    /// Bundled syntaxes plus the user's, `None` when there are no user syntaxes
+    user_syntaxes: Option<SyntaxSet>,
+    theme: Theme,
+    /// File name globs and the name of the syntax they map to
+    syntax_map: Vec<(Pattern, String)>,
+}
+
+impl Highlighter {
+    pub fn from_config(preview: &PreviewConfig, config_dir: Option<&Path>) -> io::Result<Self> {
+        let assets_dir = match &preview.assets_dir {
+            Some(dir) => Some(expand_path(
+                &dir.to_string_lossy(),
+                config_dir.unwrap_or(Path::new("/")),
+            )),
+            None => config_dir.map(Path::to_path_buf),
+        };
+
+        let mut user_syntaxes = None;
+        let mut user_themes = ThemeSet::new();
+        if let Some(assets_dir) = &assets_dir {
+            let syntaxes_dir = assets_dir.join("syntaxes");
+            if syntaxes_dir.is_dir() {
+                let mut builder = DEFAULT_SYNTAXES.clone().into_builder();
+                builder
+                    .add_from_folder(&syntaxes_dir, true)
+                    .map_err(|e| invalid_preview(format!("{}: {}", syntaxes_dir.display(), e)))?;
+                user_syntaxes = Some(builder.build());
+            }
+            let themes_dir = assets_dir.join("themes");
+            if themes_dir.is_dir() {
+                user_themes
+                    .add_from_folder(&themes_dir)
+                    .map_err(|e| invalid_preview(format!("{}: {}", themes_dir.display(), e)))?;
+            }
+        }
+        let syntaxes = user_syntaxes.as_ref().unwrap_or(&DEFAULT_SYNTAXES);
+
+        let theme = user_themes
+            .themes
+            .remove(&preview.theme)
+            .or_else(|| DEFAULT_THEMES.themes.get(&preview.theme).cloned())
+            .ok_or_else(|| {
+                invalid_preview(format!(
+                    "preview.theme: unknown theme \"{}\"",
+                    preview.theme
+                ))
+            })?;
+
+        let syntax_map = preview
+            .syntax_map
+            .iter()
+            .map(|(pattern, syntax)| {
+                let pattern = Pattern::new(pattern).map_err(|e| {
+                    invalid_preview(format!("preview.syntax_map: \"{}\": {}", pattern, e))
+                })?;
+                let syntax = syntaxes
+                    .find_syntax_by_name(syntax)
+                    .or_else(|| syntaxes.find_syntax_by_extension(syntax))
+                    .ok_or_else(|| {
+                        invalid_preview(format!(
+                            "preview.syntax_map: unknown syntax \"{}\"",
+                            syntax
+                        ))
+                    })?;
+                Ok((pattern, syntax.name.clone()))
             })
-            .collect();
-        lines.push(Line::from(spans));
+            .collect::<io::Result<Vec<_>>>()?;
+
+        Ok(Highlighter {
+            user_syntaxes,
+            theme,
+            syntax_map,
+        })
+    }
+
+    /// Highlighted lines from `first_line` on; the ones above are still
+    /// highlighted so multi-line constructs keep their colours.
+    pub fn highlight_file(
+        &self,
+        file_path: &Path,
+        max_bytes: usize,
+        first_line: usize,
+    ) -> io::Result<Vec<Line<'static>>> {
+        if is_binary_file(file_path)? {
+            return Ok(vec![Line::from("Binary or unsupported file")]);
+        }
+        let file = File::open(file_path)?;
+        let mut bytes = Vec::new();
+        file.take(max_bytes as u64).read_to_end(&mut bytes)?;
+        // the limit can cut the last character in two
+        if let Err(e) = std::str::from_utf8(&bytes) {
+            if e.error_len().is_none() {
+                bytes.truncate(e.valid_up_to());
+            }
+        }
+        let content = String::from_utf8_lossy(&bytes).replace('\t', "        ");
+
+        let ps = self.syntaxes();
+        let syntax = self.find_syntax(file_path);
+
+        let mut h = HighlightLines::new(syntax, &self.theme);
+
+        let mut lines = Vec::new();
+        for line in content.lines().take(first_line + 50) {
+            let ranges: Vec<(SyntectStyle, &str)> = h
+                .highlight_line(line, ps)
+                .unwrap_or_else(|_| vec![(SyntectStyle::default(), line)]);
+            let spans: Vec<Span> = ranges
+                .into_iter()
+                .map(|(style, text)| {
+                    Span::styled(
+                        text.to_string(),
+                        Style::default().fg(Color::Rgb(
+                            style.foreground.r,
+                            style.foreground.g,
+                            style.foreground.b,
+                        )),
+                    )
+                })
+                .collect();
+            lines.push(Line::from(spans));
+        }
+
+        Ok(lines.split_off(first_line.min(lines.len())))
+    }
+
+    fn syntaxes(&self) -> &SyntaxSet {
+        self.user_syntaxes.as_ref().unwrap_or(&DEFAULT_SYNTAXES)
+    }
+
+    fn find_syntax(&self, file_path: &Path) -> &SyntaxReference {
+        let ps = self.syntaxes();
+        let file_name = file_path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+
+        self.syntax_map
+            .iter()
+            .find(|(pattern, _)| pattern.matches(&file_name))
+            .and_then(|(_, syntax)| ps.find_syntax_by_name(syntax))
+            .or_else(|| ps.find_syntax_for_file(file_path).ok().flatten())
+            .unwrap_or_else(|| ps.find_syntax_plain_text())
+    }
+}
+
+impl Default for Highlighter {
+    fn default() -> Self {
+        Highlighter::from_config(&PreviewConfig::default(), None)
+            .expect("default preview theme is bundled with syntect")
     }
+}
 
-    Ok(lines)
+fn invalid_preview(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
 }
 
-fn is_binary_file(file_path: &str) -> io::Result<bool> {
-    let mut file = File::open(file_path)?;
-    let mut buffer = [0u8; 1024];
-    let bytes_read = file.read(&mut buffer)?;
-    Ok(buffer[..bytes_read]
-        .iter()
-        .any(|&b| b == 0 || !b.is_ascii()))
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::*;
+
+    #[test]
+    fn syntax_map_overrides_detection() {
+        let preview = PreviewConfig {
+            syntax_map: IndexMap::from([
+                ("Justfile".to_string(), "Makefile".to_string()),
+                ("*.tpl".to_string(), "html".to_string()),
+            ]),
+            ..PreviewConfig::default()
+        };
+        let highlighter = Highlighter::from_config(&preview, None).unwrap();
+
+        assert_eq!(
+            highlighter.find_syntax(Path::new("/x/Justfile")).name,
+            "Makefile"
+        );
+        assert_eq!(highlighter.find_syntax(Path::new("page.tpl")).name, "HTML");
+        assert_eq!(highlighter.find_syntax(Path::new("main.rs")).name, "Rust");
+        assert_eq!(
+            highlighter.find_syntax(Path::new("notes")).name,
+            "Plain Text"
+        );
+    }
+
+    #[test]
+    fn character_cut_by_the_limit_is_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        let mut content = "a".repeat(2047);
+        content.push('é');
+        std::fs::write(&path, &content).unwrap();
+
+        let highlighter = Highlighter::from_config(&PreviewConfig::default(), None).unwrap();
+        let lines = highlighter.highlight_file(&path, 2048, 0).unwrap();
+        assert_eq!(lines[0].to_string(), "a".repeat(2047));
+    }
+
+    #[test]
+    fn unknown_theme_or_syntax_is_an_error() {
+        let bad_theme = PreviewConfig {
+            theme: "nope".to_string(),
+            ..PreviewConfig::default()
+        };
+        assert!(Highlighter::from_config(&bad_theme, None).is_err());
+
+        let bad_syntax = PreviewConfig {
+            syntax_map: IndexMap::from([("*.x".to_string(), "Klingon".to_string())]),
+            ..PreviewConfig::default()
+        };
+        let err = Highlighter::from_config(&bad_syntax, None).unwrap_err();
+        assert!(err.to_string().contains("unknown syntax \"Klingon\""));
+    }
+
+    #[test]
+    fn loads_user_themes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("themes")).unwrap();
+        std::fs::write(
+            dir.path().join("themes").join("Mine.tmTheme"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0"><dict>
+<key>name</key><string>Mine</string>
+<key>settings</key><array><dict><key>settings</key><dict>
+<key>foreground</key><string>#112233</string>
+</dict></dict></array>
+</dict></plist>"#,
+        )
+        .unwrap();
+
+        let preview = PreviewConfig {
+            theme: "Mine".to_string(),
+            ..PreviewConfig::default()
+        };
+        assert!(Highlighter::from_config(&preview, Some(dir.path())).is_ok());
+    }
 }
@@ -2,10 +2,12 @@ pub mod body;
 pub mod file_preview;
 pub mod modal;
 
+use std::rc::Rc;
+
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     prelude::{Frame, Rect},
-    style::{Color, Style, Stylize},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Widget},
 };
@@ -19,32 +21,56 @@ use crate::app::{
     },
     state::{Mode, State},
     ui::{
-        body::{bookmarks::Bookmarks, Body},
-        modal::Modal,
+        body::{bookmarks::Bookmarks, dual_pane::DualPaneView, Body},
+        modal::{hint_bar, Modal},
     },
     utils::{format_bytes, fs::whoami_info, i18n::Lang},
 };
 
-pub fn render(state: &State, frame: &mut Frame<'_>) {
-    let area = frame.area();
-
-    let layout = Layout::default()
+fn layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(HEADER_HEIGHT),
             Constraint::Min(0),
             Constraint::Length(FOOTER_HEIGHT),
         ])
-        .split(area);
+        .split(area)
+}
+
+/// Part of the screen between the header and the footer.
+pub fn body_area(area: Rect) -> Rect {
+    layout(area)[1]
+}
+
+pub fn render(state: &State, frame: &mut Frame<'_>) {
+    let area = frame.area();
+    let layout = layout(area);
 
     let header = Header::build(state, layout[0]);
     let body = Body::build(state, layout[1]);
     let footer = Footer::build(state, layout[2]);
 
-    frame.render_widget(header, layout[0]);
+    if state.tabs.len() > 1 {
+        let tab_bar = TabBar::build(state);
+        let header_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(tab_bar.width() as u16),
+            ])
+            .split(layout[0]);
+        frame.render_widget(header, header_layout[0]);
+        frame.render_widget(tab_bar, header_layout[1]);
+    } else {
+        frame.render_widget(header, layout[0]);
+    }
     if let Mode::Bookmarks { position_id } = state.mode {
         let bookmarks = Bookmarks::build(state, position_id, layout[1]);
         frame.render_widget(bookmarks, layout[1]);
+    } else if let Some(dual) = &state.dual_pane {
+        let panes = DualPaneView::build(state, dual, layout[1]);
+        frame.render_widget(panes, layout[1]);
     } else {
         frame.render_widget(body, layout[1]);
     }
@@ -54,9 +80,14 @@ pub fn render(state: &State, frame: &mut Frame<'_>) {
         let modal = Modal::build(state, area);
         frame.render_widget(modal, area);
     }
+
+    if !state.pending_keys.keys.is_empty() {
+        hint_bar::build_pending(area, frame.buffer_mut(), state);
+    }
 }
 
 pub struct Header;
+pub struct TabBar;
 pub struct Footer;
 
 impl Header {
@@ -68,12 +99,40 @@ impl Header {
 
         let user_info = whoami_info().unwrap_or_else(|_| String::from("unknown@localhost"));
 
-        let text = Line::from(vec![
-            Span::styled(user_info, Style::default().fg(Color::Green).bold()),
+        let mut spans = vec![
+            Span::styled(user_info, state.theme.header_user),
             Span::raw(" "),
-            Span::styled(format!("{}/", dir), Style::default().fg(Color::Blue).bold()),
-            Span::raw(file).bold(),
-        ]);
+            Span::styled(format!("{}/", dir), state.theme.header_path),
+            Span::styled(file, state.theme.header_file),
+        ];
+        if let Some(matcher) = state.views.session_filter(&state.current_dir) {
+            spans.extend(vec![
+                Span::raw(" "),
+                Span::styled(
+                    Lang::en_fmt("filter_active", &[matcher.query()]),
+                    state.theme.header_filter,
+                ),
+            ]);
+        }
+        if let Some(find) = &state.find {
+            spans.extend(vec![
+                Span::raw(" "),
+                Span::styled(
+                    Lang::en_fmt("find_active", &[find.query.terms()]),
+                    state.theme.header_filter,
+                ),
+            ]);
+            if find.truncated {
+                spans.extend(vec![
+                    Span::raw(" "),
+                    Span::styled(
+                        Lang::en_fmt("truncated", &[&find.entries.len().to_string()]),
+                        state.theme.header_filter,
+                    ),
+                ]);
+            }
+        }
+        let text = Line::from(spans);
 
         Paragraph::new(text)
             .block(Block::default())
@@ -81,18 +140,38 @@ impl Header {
     }
 }
 
+impl TabBar {
+    fn build(state: &State) -> Line<'static> {
+        let spans: Vec<Span> = state
+            .tabs
+            .titles(&state.current_dir)
+            .into_iter()
+            .enumerate()
+            .map(|(id, title)| {
+                let style = if id == state.tabs.active {
+                    state.theme.tab_active
+                } else {
+                    state.theme.tab
+                };
+                Span::styled(format!(" {}:{} ", id + 1, title), style)
+            })
+            .collect();
+        Line::from(spans)
+    }
+}
+
 impl Footer {
     fn build<'a>(state: &'a State, _area: Rect) -> impl Widget + 'a {
         if let Some(notification) = &state.notification {
-            let (msg, color) = match notification {
-                Notification::Info { msg } => (msg, Color::White),
-                Notification::Success { msg } => (msg, Color::LightGreen),
-                Notification::Warn { msg } => (msg, Color::LightYellow),
-                Notification::Error { msg } => (msg, Color::LightRed),
+            let (msg, style): (_, Style) = match notification {
+                Notification::Info { msg } => (msg, state.theme.notification_info),
+                Notification::Success { msg } => (msg, state.theme.notification_success),
+                Notification::Warn { msg } => (msg, state.theme.notification_warn),
+                Notification::Error { msg } => (msg, state.theme.notification_error),
             };
 
             Paragraph::new(msg.as_ref())
-                .style(Style::default().fg(color))
+                .style(style)
                 .block(Block::default().borders(Borders::NONE))
                 .alignment(Alignment::Left)
         } else {
@@ -123,20 +202,27 @@ impl Footer {
                     .unwrap_or_default();
 
             let mut spans = vec![
-                Span::styled(permissions, Style::default().fg(Color::LightBlue).bold()),
+                Span::styled(permissions, state.theme.footer_perms),
                 Span::raw(" "),
-                Span::styled(last_modified, Style::default().fg(Color::White).bold()),
+                Span::styled(last_modified, state.theme.footer_mtime),
                 Span::raw(" "),
-                Span::styled(size, Style::default().fg(Color::LightGreen).bold()),
+                Span::styled(size, state.theme.footer_size),
             ];
 
+            if !state.pending_keys.is_empty() {
+                spans.extend(vec![
+                    Span::raw(" "),
+                    Span::styled(state.pending_keys.to_string(), state.theme.footer_pending),
+                ]);
+            }
+
             if state.mode == Mode::Search && state.search_pattern.is_some() {
                 let matched = count_matched_files(&state.files[1]);
                 if matched > 0 {
                     let matches = Lang::en_fmt("matches", &[&matched.to_string()]);
                     spans.extend(vec![
                         Span::raw(" "),
-                        Span::styled(matches, Style::default().fg(Color::Red).bold()),
+                        Span::styled(matches, state.theme.footer_matches),
                     ]);
                 }
             }
@@ -0,0 +1,50 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, List, ListItem, Widget},
+};
+
+use crate::app::{state::goto::PathCompletion, utils::config_parser::theme::Theme};
+
+const MAX_ROWS: u16 = 10;
+
+/// Candidates of the go-to prompt, listed right above the bottom line.
+pub fn build(area: Rect, buf: &mut Buffer, completion: &PathCompletion, line: &str, theme: &Theme) {
+    let candidates = completion.visible(line);
+    if candidates.is_empty() {
+        return;
+    }
+    let height = (candidates.len() as u16)
+        .min(MAX_ROWS)
+        .min(area.height.saturating_sub(1));
+
+    let list_area = Rect {
+        x: 0,
+        y: area.height.saturating_sub(height + 1),
+        height,
+        width: area.width,
+    };
+
+    Clear.render(list_area, buf);
+
+    let selected = completion.selected.unwrap_or(0);
+    let offset = (selected + 1).saturating_sub(height as usize);
+
+    let items: Vec<ListItem> = candidates
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(height as usize)
+        .map(|(id, name)| {
+            let style = if completion.selected == Some(id) {
+                theme.selected_dir
+            } else {
+                theme.dir
+            };
+            ListItem::new(Line::from(Span::styled(format!(" {}/", name), style)))
+        })
+        .collect();
+
+    List::new(items).render(list_area, buf);
+}
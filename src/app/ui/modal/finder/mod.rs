@@ -0,0 +1,117 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Widget},
+};
+
+use crate::app::{
+    model::finder::{Finder, FinderEntry, FinderMatch},
+    state::State,
+    utils::config_parser::theme::Theme,
+};
+
+const PROMPT: &str = "> ";
+
+/// Ranked paths above a query line, covering the lower two thirds of the screen.
+pub fn build(area: Rect, buf: &mut Buffer, finder: &Finder, state: &State) {
+    let theme = &state.theme;
+    let height = (area.height * 2 / 3).max(4).min(area.height);
+    let modal_area = Rect {
+        x: 0,
+        y: area.height.saturating_sub(height),
+        height,
+        width: area.width,
+    };
+
+    Clear.render(modal_area, buf);
+
+    let status = if finder.is_walking() {
+        format!(" {}/{} … ", finder.matches.len(), finder.entries.len())
+    } else {
+        format!(" {}/{} ", finder.matches.len(), finder.entries.len())
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Find ")
+        .title_bottom(Line::from(status).right_aligned())
+        .style(theme.modal_border);
+    let inner = block.inner(modal_area);
+    block.render(modal_area, buf);
+
+    let list_area = Rect {
+        height: inner.height.saturating_sub(1),
+        ..inner
+    };
+    let visible = list_area.height as usize;
+    let offset = (finder.selected + 1).saturating_sub(visible);
+
+    let items: Vec<ListItem> = finder
+        .matches
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(id, m)| {
+            let entry = &finder.entries[m.entry];
+            ListItem::new(row(entry, m, id == finder.selected, theme))
+        })
+        .collect();
+    List::new(items).render(list_area, buf);
+
+    let prompt_area = Rect {
+        y: inner.y + list_area.height,
+        height: 1,
+        width: (PROMPT.len() as u16).min(inner.width),
+        ..inner
+    };
+    Span::styled(PROMPT, theme.prompt).render(prompt_area, buf);
+
+    let input_area = Rect {
+        x: prompt_area.x + prompt_area.width,
+        width: inner.width.saturating_sub(prompt_area.width),
+        ..prompt_area
+    };
+    let mut input = state.input.clone();
+    input.set_block(Block::default().style(theme.prompt));
+    input.set_cursor_line_style(Style::default());
+    input.render(input_area, buf);
+}
+
+/// The path with the chars that matched the query picked out.
+fn row<'a>(entry: &'a FinderEntry, m: &FinderMatch, selected: bool, theme: &Theme) -> Line<'a> {
+    let base = match (entry.is_dir, selected) {
+        (true, true) => theme.selected_dir,
+        (true, false) => theme.dir,
+        (false, true) => theme.selected_file,
+        (false, false) => theme.file,
+    };
+    let matched = base.patch(theme.matched);
+
+    let mut spans: Vec<Span> = vec![Span::styled(" ", base)];
+    let mut indices = m.indices.iter().peekable();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (id, c) in entry.path.chars().enumerate() {
+        let is_match = indices.next_if_eq(&&id).is_some();
+        if is_match != run_matched && !run.is_empty() {
+            let style = if run_matched { matched } else { base };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_matched = is_match;
+        run.push(c);
+    }
+    if entry.is_dir {
+        if run_matched {
+            spans.push(Span::styled(std::mem::take(&mut run), matched));
+        }
+        run.push('/');
+        run_matched = false;
+    }
+    if !run.is_empty() {
+        let style = if run_matched { matched } else { base };
+        spans.push(Span::styled(run, style));
+    }
+    Line::from(spans)
+}
@@ -0,0 +1,64 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Widget},
+};
+
+use crate::app::{
+    model::grep::Grep,
+    utils::{config_parser::theme::Theme, i18n::Lang},
+};
+
+/// `path:line:text` rows covering the lower two thirds of the screen.
+pub fn build(area: Rect, buf: &mut Buffer, grep: &Grep, theme: &Theme) {
+    let height = (area.height * 2 / 3).max(3).min(area.height);
+    let modal_area = Rect {
+        x: 0,
+        y: area.height.saturating_sub(height),
+        height,
+        width: area.width,
+    };
+
+    Clear.render(modal_area, buf);
+
+    let count = grep.hits.len().to_string();
+    let status = if grep.truncated {
+        format!(" {} ", Lang::en_fmt("truncated", &[&count]))
+    } else if grep.is_searching() {
+        format!(" {} … ", count)
+    } else {
+        format!(" {} ", count)
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" grep: {} ", grep.query()))
+        .title_bottom(Line::from(status).right_aligned())
+        .style(theme.modal_border);
+    let inner = block.inner(modal_area);
+    block.render(modal_area, buf);
+
+    // keep the selection in view when the list is taller than the screen
+    let visible = inner.height as usize;
+    let offset = (grep.selected + 1).saturating_sub(visible);
+
+    let items: Vec<ListItem> = grep
+        .hits
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(id, hit)| {
+            let (location, text) = if id == grep.selected {
+                (theme.selected_file, theme.selected_file)
+            } else {
+                (theme.dir, theme.hint_text)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {}:{}:", hit.path.display(), hit.line), location),
+                Span::styled(hit.text.as_str(), text),
+            ]))
+        })
+        .collect();
+    List::new(items).render(inner, buf);
+}
@@ -1,10 +1,22 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Rect},
-    style::{Color, Style},
+    style::Style,
     widgets::{Block, Borders, Cell, Clear, Row, Table, Widget},
 };
 
+use crate::app::{
+    state::State,
+    utils::config_parser::{
+        keymap::{Action, KeymapMode},
+        theme::Theme,
+    },
+};
+
+const HEIGHT: u16 = 10;
+/// Rows taken by the top border and the table header
+const FIRST_ROW: u16 = 2;
+
 #[derive(Debug)]
 pub enum HintBarMode {
     Bookmarks,
@@ -12,13 +24,81 @@ pub enum HintBarMode {
     Exit,
 }
 
-pub fn build(area: Rect, buf: &mut Buffer, mode: &HintBarMode) {
-    let height = 10;
+fn entries(mode: &HintBarMode) -> (KeymapMode, Vec<(Action, &'static str)>) {
+    match mode {
+        HintBarMode::Bookmarks => (
+            KeymapMode::BookmarksMenu,
+            vec![
+                (Action::BookmarkList, "Bookmark List"),
+                (Action::BookmarkAdd, "Add Bookmark"),
+            ],
+        ),
+        HintBarMode::Delete => (
+            KeymapMode::DeleteMenu,
+            vec![
+                (Action::Cut, "Cut Files"),
+                (
+                    Action::Trash,
+                    "Delete Files To Trash (On macOS, if prompted, please grant file acces. If not granted, files will be deleted permanently.)",
+                ),
+                (Action::Delete, "Delete Files Permanently"),
+            ],
+        ),
+        HintBarMode::Exit => (
+            KeymapMode::ExitMenu,
+            vec![
+                (Action::QuitToCurrentDir, "Exit into current directory"),
+                (Action::Quit, "Exit into initial directory"),
+            ],
+        ),
+    }
+}
+
+/// Action of the menu row drawn at screen row `y`.
+pub fn action_at(area: Rect, mode: &HintBarMode, y: u16) -> Option<Action> {
+    let top = area.height.saturating_sub(HEIGHT) + FIRST_ROW;
+    let row = y.checked_sub(top)?;
+    entries(mode).1.get(row as usize).map(|(action, _)| *action)
+}
+
+pub fn build(area: Rect, buf: &mut Buffer, mode: &HintBarMode, state: &State) {
+    let (keymap_mode, list) = entries(mode);
+
+    let rows: Vec<(String, String)> = list
+        .iter()
+        .map(|(action, description)| {
+            let key = state
+                .keymap
+                .keys_for(keymap_mode, *action)
+                .iter()
+                .map(|k| k.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            (key, (*description).to_string())
+        })
+        .collect();
+
+    render_table(area, buf, rows, &state.theme);
+}
+
+/// Which-key style list of the bindings that can complete the keys typed so far.
+pub fn build_pending(area: Rect, buf: &mut Buffer, state: &State) {
+    let mode = state.keymap_mode();
+    let rows: Vec<(String, String)> = state
+        .keymap
+        .continuations(mode, &state.pending_keys.keys)
+        .iter()
+        .map(|(rest, action)| (rest.to_string(), action.description().to_string()))
+        .collect();
+
+    render_table(area, buf, rows, &state.theme);
+}
 
+fn render_table(area: Rect, buf: &mut Buffer, list: Vec<(String, String)>, theme: &Theme) {
     let modal_area = Rect {
         x: 0,
-        y: area.height.saturating_sub(height),
-        height,
+        y: area.height.saturating_sub(HEIGHT),
+        height: HEIGHT,
         width: area.width,
     };
 
@@ -27,32 +107,36 @@ pub fn build(area: Rect, buf: &mut Buffer, mode: &HintBarMode) {
     let backdrop = Block::default().style(Style::default());
     backdrop.render(modal_area, buf);
 
-    let list = match mode {
-        HintBarMode::Bookmarks => vec![("b", "Bookmark List"), ("a", "Add Bookmark")],
-        HintBarMode::Delete => vec![
-            ("d", "Cut Files"),
-            (
-                "D",
-                "Delete Files To Trash (On macOS, if prompted, please grant file acces. If not granted, files will be deleted permanently.)",
-            ),
-            ("x", "Delete Files Permanently"),
-        ],
-        HintBarMode::Exit => vec![("Z", "Exit into current directory"), ("Q", "Exit into initial directory")],
-    };
-
     let rows: Vec<Row> = list
-        .iter()
-        .map(|(key, action)| {
+        .into_iter()
+        .map(|(key, description)| {
             Row::new(vec![
-                Cell::from(format!(" {key}")).style(Style::default().fg(Color::Yellow)),
-                Cell::from((*action).to_string()),
+                Cell::from(format!(" {key}")).style(theme.hint_key),
+                Cell::from(description),
             ])
         })
         .collect();
 
     Table::new(rows, [Constraint::Length(12), Constraint::Min(10)])
-        .header(Row::new(vec!["Key", "Action"]).style(Style::default().fg(Color::Cyan)))
+        .header(Row::new(vec!["Key", "Action"]).style(theme.hint_header))
         .block(Block::default().borders(Borders::TOP))
-        .style(Style::default().fg(Color::White))
+        .style(theme.hint_text)
         .render(modal_area, buf);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_map_to_menu_actions() {
+        let area = Rect::new(0, 0, 80, 30);
+        assert_eq!(action_at(area, &HintBarMode::Exit, 21), None);
+        assert_eq!(
+            action_at(area, &HintBarMode::Exit, 22),
+            Some(Action::QuitToCurrentDir)
+        );
+        assert_eq!(action_at(area, &HintBarMode::Exit, 23), Some(Action::Quit));
+        assert_eq!(action_at(area, &HintBarMode::Exit, 24), None);
+    }
+}
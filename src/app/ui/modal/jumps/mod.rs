@@ -0,0 +1,56 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Widget},
+};
+
+use crate::app::{model::jumps::JumpList, utils::config_parser::theme::Theme};
+
+pub fn build(area: Rect, buf: &mut Buffer, jumps: &JumpList, selected: usize, theme: &Theme) {
+    let entries = jumps.entries();
+    let height = (entries.len() as u16 + 2).min(area.height);
+
+    let modal_area = Rect {
+        x: 0,
+        y: area.height.saturating_sub(height),
+        height,
+        width: area.width,
+    };
+
+    Clear.render(modal_area, buf);
+
+    // keep the selection in view when the list is taller than the screen
+    let visible = height.saturating_sub(2) as usize;
+    let offset = (selected + 1).saturating_sub(visible);
+
+    let items: Vec<ListItem> = entries
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(id, dir)| {
+            let marker = if jumps.current() == Some(id) {
+                ">"
+            } else {
+                " "
+            };
+            let style = if id == selected {
+                theme.selected_file
+            } else {
+                theme.hint_text
+            };
+            let entry = format!("{} {:>3}  {}", marker, id + 1, dir.display());
+            ListItem::new(Line::from(Span::styled(entry, style)))
+        })
+        .collect();
+
+    List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Jumps ")
+                .style(theme.modal_border),
+        )
+        .render(modal_area, buf);
+}
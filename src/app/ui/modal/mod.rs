@@ -1,14 +1,25 @@
+pub mod completions;
+pub mod finder;
+pub mod grep;
 pub mod hint_bar;
+pub mod jumps;
+pub mod open_with;
+
+use std::path::PathBuf;
 
 use crate::app::{
     config::constants::ui::{COLUMN_PERCENTAGE, FIRST_COLUMN_PERCENTAGE, HEADER_HEIGHT},
-    model::miller::positions::get_position,
-    state::State,
+    model::{
+        file::count_matched_files, finder::Finder, grep::Grep, miller::positions::get_position,
+    },
+    state::{goto::PathCompletion, State},
+    utils::{config_parser::opener::Opener, i18n::Lang},
 };
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
-    style::{Color, Style, Stylize},
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Line, Span},
     widgets::{Block, Borders, Clear, Widget},
 };
 
@@ -19,11 +30,54 @@ pub enum UnderLineModalAction {
     Bookmarks,
 }
 
+#[derive(Debug, PartialEq)]
+pub enum BottomLineModalAction {
+    Search,
+    Command,
+    /// Free-form command for the "Open with" picker
+    OpenWith {
+        files: Vec<PathBuf>,
+    },
+    GoTo {
+        completion: PathCompletion,
+    },
+    /// Keywords for a jump to a frecent directory
+    Frecency,
+    /// Query that narrows the current directory
+    Filter,
+    /// Text to look for in the files under the current directory
+    Grep,
+}
+
 #[derive(Debug)]
 pub enum ModalKind {
-    UnderLine { action: UnderLineModalAction },
-    HintBar { mode: hint_bar::HintBarMode },
-    BottomLine,
+    UnderLine {
+        action: UnderLineModalAction,
+    },
+    HintBar {
+        mode: hint_bar::HintBarMode,
+    },
+    BottomLine {
+        action: BottomLineModalAction,
+    },
+    /// `choices` are the matching opener rules; one past the last is the custom command entry.
+    OpenWith {
+        files: Vec<PathBuf>,
+        choices: Vec<Opener>,
+        selected: usize,
+    },
+    /// Picker over `State::jumps`
+    Jumps {
+        selected: usize,
+    },
+    /// Fuzzy finder over the tree under the current directory; the query is `State::input`
+    Finder {
+        finder: Finder,
+    },
+    /// Lines found by a content search, filled in as the search goes
+    Grep {
+        grep: Grep,
+    },
     Disabled,
     // Custom { frame: ModalFrame },
 }
@@ -41,8 +95,24 @@ impl ModalKind {
         matches!(self, ModalKind::HintBar { .. })
     }
 
+    pub fn is_open_with(&self) -> bool {
+        matches!(self, ModalKind::OpenWith { .. })
+    }
+
+    pub fn is_jumps(&self) -> bool {
+        matches!(self, ModalKind::Jumps { .. })
+    }
+
+    pub fn is_finder(&self) -> bool {
+        matches!(self, ModalKind::Finder { .. })
+    }
+
+    pub fn is_grep(&self) -> bool {
+        matches!(self, ModalKind::Grep { .. })
+    }
+
     pub fn is_bottom_line(&self) -> bool {
-        matches!(self, ModalKind::BottomLine)
+        matches!(self, ModalKind::BottomLine { .. })
     }
 
     pub fn is_enabled(&self) -> bool {
@@ -101,14 +171,24 @@ impl<'a> Widget for Modal<'a> {
                     Block::default()
                         .borders(Borders::ALL)
                         .title(title)
-                        .style(Style::default().fg(Color::LightGreen).bold()),
+                        .style(self.state.theme.modal_border),
                 );
                 input.set_cursor_line_style(Style::default());
 
                 input.render(modal_area, buf);
             }
-            ModalKind::HintBar { mode } => hint_bar::build(area, buf, mode),
-            ModalKind::BottomLine => {
+            ModalKind::HintBar { mode } => hint_bar::build(area, buf, mode, self.state),
+            ModalKind::OpenWith {
+                files,
+                choices,
+                selected,
+            } => open_with::build(area, buf, files, choices, *selected, &self.state.theme),
+            ModalKind::Jumps { selected } => {
+                jumps::build(area, buf, &self.state.jumps, *selected, &self.state.theme)
+            }
+            ModalKind::Finder { finder } => finder::build(area, buf, finder, self.state),
+            ModalKind::Grep { grep } => grep::build(area, buf, grep, &self.state.theme),
+            ModalKind::BottomLine { action } => {
                 let modal_area = Rect {
                     x: 0,
                     y: area.height.saturating_sub(1),
@@ -121,12 +201,57 @@ impl<'a> Widget for Modal<'a> {
                 let backdrop = Block::default().style(Style::default());
                 backdrop.render(modal_area, buf);
 
+                let prefix = match action {
+                    BottomLineModalAction::Search => "/",
+                    BottomLineModalAction::Command => ":",
+                    BottomLineModalAction::OpenWith { .. } => "open with: ",
+                    BottomLineModalAction::GoTo { .. } => "go to: ",
+                    BottomLineModalAction::Frecency => "z ",
+                    BottomLineModalAction::Filter => "filter: ",
+                    BottomLineModalAction::Grep => "grep: ",
+                };
+
+                if let BottomLineModalAction::GoTo { completion } = action {
+                    let line = self.state.input.lines().join("");
+                    completions::build(area, buf, completion, &line, &self.state.theme);
+                }
+                let prefix_area = Rect {
+                    width: (prefix.len() as u16).min(modal_area.width),
+                    ..modal_area
+                };
+                Span::styled(prefix, self.state.theme.prompt).render(prefix_area, buf);
+
+                // live count where the footer would show it
+                let mut modal_area = modal_area;
+                if let (BottomLineModalAction::Search, Some(_)) =
+                    (action, &self.state.search_pattern)
+                {
+                    let matched = count_matched_files(&self.state.files[1]);
+                    let matches = Lang::en_fmt("matches", &[&matched.to_string()]);
+                    let width = (matches.len() as u16 + 1).min(modal_area.width);
+                    modal_area.width -= width;
+                    let count_area = Rect {
+                        x: modal_area.x + modal_area.width,
+                        width,
+                        ..modal_area
+                    };
+                    Line::from(Span::styled(matches, self.state.theme.footer_matches))
+                        .alignment(Alignment::Right)
+                        .render(count_area, buf);
+                }
+
+                let input_area = Rect {
+                    x: modal_area.x + prefix_area.width,
+                    width: modal_area.width.saturating_sub(prefix_area.width),
+                    ..modal_area
+                };
+
                 let mut input = self.state.input.clone();
 
-                input.set_block(Block::default().style(Style::default().fg(Color::White).bold()));
+                input.set_block(Block::default().style(self.state.theme.prompt));
                 input.set_cursor_line_style(Style::default());
 
-                input.render(modal_area, buf);
+                input.render(input_area, buf);
             }
             ModalKind::Disabled => {}
         }
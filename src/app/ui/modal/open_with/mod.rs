@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Widget},
+};
+
+use crate::app::utils::config_parser::{opener::Opener, theme::Theme};
+
+pub fn build(
+    area: Rect,
+    buf: &mut Buffer,
+    files: &[PathBuf],
+    choices: &[Opener],
+    selected: usize,
+    theme: &Theme,
+) {
+    // one row per rule, the custom command entry and the borders
+    let height = (choices.len() as u16 + 3).min(area.height);
+
+    let modal_area = Rect {
+        x: 0,
+        y: area.height.saturating_sub(height),
+        height,
+        width: area.width,
+    };
+
+    Clear.render(modal_area, buf);
+
+    let title = match files {
+        [file] => format!(
+            " Open {} with ",
+            file.file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default()
+        ),
+        _ => format!(" Open {} files with ", files.len()),
+    };
+
+    let entries = choices
+        .iter()
+        .map(|opener| {
+            let kind = if opener.rule.terminal { "" } else { " (gui)" };
+            format!(" {}{}", opener.rule.command, kind)
+        })
+        .chain(std::iter::once(" Custom command…".to_string()));
+
+    let items: Vec<ListItem> = entries
+        .enumerate()
+        .map(|(id, entry)| {
+            let style = if id == selected {
+                theme.selected_file
+            } else {
+                theme.hint_text
+            };
+            ListItem::new(Line::from(Span::styled(entry, style)))
+        })
+        .collect();
+
+    List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(theme.modal_border),
+        )
+        .render(modal_area, buf);
+}
@@ -1,17 +1,128 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug, Clone)] // config container
+use crate::app::{
+    model::matcher::MatchEngine,
+    utils::config_parser::{keymap::KeysConfig, opener::OpenerRule, theme::ThemeConfig},
+};
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)] // config container
 pub struct Config {
     pub common: CommonConfig,
     pub bookmarks: IndexMap<String, PathBuf>,
+    #[serde(default)]
+    pub keys: KeysConfig,
+    #[serde(default, skip_serializing_if = "is_default_theme")]
+    pub theme: ThemeConfig,
+    #[serde(default, skip_serializing_if = "is_default_preview")]
+    pub preview: PreviewConfig,
+    /// Tried in order when opening a file; see [`OpenerRule`].
+    #[serde(
+        default = "default_openers",
+        skip_serializing_if = "is_default_openers"
+    )]
+    pub openers: Vec<OpenerRule>,
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+    /// `--editor`, used instead of `common.editor` but never saved
+    #[serde(skip)]
+    pub editor_override: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct CommonConfig {
     pub editor: String,
+    /// Milliseconds to wait for the next key of a multi-key binding.
+    #[serde(default = "default_key_timeout")]
+    pub key_timeout: u64,
+    /// Whether the fuzzy finder skips what `.gitignore` and `.ignore` files exclude.
+    #[serde(default = "default_respect_ignore")]
+    pub respect_ignore: bool,
+    /// Whether to capture the mouse; off leaves selection to the terminal.
+    #[serde(default = "default_mouse")]
+    pub mouse: bool,
+    /// How `/` matches names: substring, prefix, glob, regex or fuzzy.
+    #[serde(default)]
+    pub search: MatchEngine,
+}
+
+/// File preview highlighting. Extra `.tmTheme` files are loaded from
+/// `<assets_dir>/themes` and `.sublime-syntax` files from `<assets_dir>/syntaxes`;
+/// `assets_dir` defaults to the directory of the config file.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PreviewConfig {
+    pub theme: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assets_dir: Option<PathBuf>,
+    /// File name or glob (`Justfile`, `*.tpl`) to syntax name or extension.
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub syntax_map: IndexMap<String, String>,
+}
+
+impl Default for PreviewConfig {
+    fn default() -> Self {
+        PreviewConfig {
+            theme: "base16-ocean.dark".to_string(),
+            assets_dir: None,
+            syntax_map: IndexMap::new(),
+        }
+    }
+}
+
+fn is_default_preview(preview: &PreviewConfig) -> bool {
+    *preview == PreviewConfig::default()
+}
+
+fn is_default_theme(theme: &ThemeConfig) -> bool {
+    *theme == ThemeConfig::default()
+}
+
+fn default_key_timeout() -> u64 {
+    1000
+}
+
+/// Text files open in the editor; anything no rule matches goes to the
+/// system default application.
+fn default_openers() -> Vec<OpenerRule> {
+    vec![OpenerRule {
+        ext: vec![],
+        glob: None,
+        mime: None,
+        executable: None,
+        text: Some(true),
+        command: "{editor}".to_string(),
+        terminal: true,
+        wait: false,
+    }]
+}
+
+fn is_default_openers(openers: &[OpenerRule]) -> bool {
+    openers == default_openers()
+}
+
+fn default_respect_ignore() -> bool {
+    true
+}
+
+fn default_mouse() -> bool {
+    true
+}
+
+impl Config {
+    /// Directory holding the config file, also where user assets are looked up.
+    pub fn dir(&self) -> Option<&Path> {
+        self.path.as_deref().and_then(Path::parent)
+    }
+
+    /// The editor to launch: `--editor` when given, otherwise the configured one.
+    pub fn editor(&self) -> &str {
+        self.editor_override
+            .as_deref()
+            .unwrap_or(&self.common.editor)
+    }
 }
 
 impl Default for Config {
@@ -19,8 +130,18 @@ impl Default for Config {
         Config {
             common: CommonConfig {
                 editor: "nvim".to_string(),
+                key_timeout: default_key_timeout(),
+                respect_ignore: default_respect_ignore(),
+                mouse: default_mouse(),
+                search: MatchEngine::default(),
             },
             bookmarks: IndexMap::new(),
+            keys: KeysConfig::default(),
+            theme: ThemeConfig::default(),
+            preview: PreviewConfig::default(),
+            openers: default_openers(),
+            path: None,
+            editor_override: None,
         }
     }
 }
@@ -0,0 +1,1021 @@
+use std::{
+    fmt, io,
+    time::{Duration, Instant},
+};
+
+use indexmap::IndexMap;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+use crate::app::{
+    state::Mode,
+    ui::modal::{hint_bar::HintBarMode, BottomLineModalAction, ModalKind},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeymapMode {
+    Normal,
+    Visual,
+    Insert,
+    /// The `:` and go-to prompts, which complete with Tab
+    CommandLine,
+    Bookmarks,
+    BookmarksMenu,
+    DeleteMenu,
+    ExitMenu,
+    OpenWith,
+    Jumps,
+    Finder,
+    Grep,
+}
+
+impl KeymapMode {
+    pub fn from_state(mode: &Mode, modal_type: &ModalKind) -> Self {
+        match modal_type {
+            ModalKind::OpenWith { .. } => return KeymapMode::OpenWith,
+            ModalKind::Jumps { .. } => return KeymapMode::Jumps,
+            ModalKind::Finder { .. } => return KeymapMode::Finder,
+            ModalKind::Grep { .. } => return KeymapMode::Grep,
+            _ => {}
+        }
+        match mode {
+            Mode::Normal | Mode::Search => match modal_type {
+                ModalKind::HintBar { mode } => match mode {
+                    HintBarMode::Bookmarks => KeymapMode::BookmarksMenu,
+                    HintBarMode::Delete => KeymapMode::DeleteMenu,
+                    HintBarMode::Exit => KeymapMode::ExitMenu,
+                },
+                _ => KeymapMode::Normal,
+            },
+            Mode::Insert => match modal_type {
+                ModalKind::BottomLine {
+                    action: BottomLineModalAction::Command | BottomLineModalAction::GoTo { .. },
+                } => KeymapMode::CommandLine,
+                _ => KeymapMode::Insert,
+            },
+            Mode::Visual { .. } => KeymapMode::Visual,
+            Mode::Bookmarks { .. } => KeymapMode::Bookmarks,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            KeymapMode::Normal => "normal",
+            KeymapMode::Visual => "visual",
+            KeymapMode::Insert => "insert",
+            KeymapMode::CommandLine => "command_line",
+            KeymapMode::Bookmarks => "bookmarks",
+            KeymapMode::BookmarksMenu => "bookmarks_menu",
+            KeymapMode::DeleteMenu => "delete_menu",
+            KeymapMode::ExitMenu => "exit_menu",
+            KeymapMode::OpenWith => "open_with",
+            KeymapMode::Jumps => "jumps",
+            KeymapMode::Finder => "finder",
+            KeymapMode::Grep => "grep",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    QuitToCurrentDir,
+    MoveUp,
+    MoveDown,
+    PageUp,
+    PageDown,
+    Parent,
+    Open,
+    Rename,
+    Add,
+    Yank,
+    Cut,
+    Paste,
+    Trash,
+    Delete,
+    ToggleVisual,
+    ToggleMark,
+    ToggleHidden,
+    Search,
+    SearchNext,
+    SearchPrev,
+    Cancel,
+    Confirm,
+    BookmarksMenu,
+    DeleteMenu,
+    ExitMenu,
+    BookmarkList,
+    BookmarkAdd,
+    BookmarkDelete,
+    CommandLine,
+    Complete,
+    CompletePrev,
+    GoTop,
+    GoBottom,
+    GoHome,
+    GoRoot,
+    OpenWith,
+    JumpBack,
+    JumpForward,
+    Jumps,
+    NewTab,
+    CloseTab,
+    NextTab,
+    PrevTab,
+    MoveTabLeft,
+    MoveTabRight,
+    ToggleDualPane,
+    SwitchPane,
+    CopyToPane,
+    MoveToPane,
+    GoTo,
+    Finder,
+    ZJump,
+    Choose,
+    Filter,
+    ClearFilter,
+    Grep,
+}
+
+const ACTIONS: [(&str, Action, &str); 57] = [
+    ("quit", Action::Quit, "Quit"),
+    (
+        "quit_to_current_dir",
+        Action::QuitToCurrentDir,
+        "Quit into current directory",
+    ),
+    ("move_up", Action::MoveUp, "Move up"),
+    ("move_down", Action::MoveDown, "Move down"),
+    ("page_up", Action::PageUp, "Page up"),
+    ("page_down", Action::PageDown, "Page down"),
+    ("parent", Action::Parent, "Go to parent directory"),
+    ("open", Action::Open, "Open"),
+    ("rename", Action::Rename, "Rename file"),
+    ("add", Action::Add, "Add file"),
+    ("yank", Action::Yank, "Copy files"),
+    ("cut", Action::Cut, "Cut files"),
+    ("paste", Action::Paste, "Paste files"),
+    ("trash", Action::Trash, "Delete files to trash"),
+    ("delete", Action::Delete, "Delete files permanently"),
+    ("toggle_visual", Action::ToggleVisual, "Toggle visual mode"),
+    ("toggle_mark", Action::ToggleMark, "Mark file"),
+    ("toggle_hidden", Action::ToggleHidden, "Toggle hidden files"),
+    ("search", Action::Search, "Search"),
+    ("search_next", Action::SearchNext, "Next match"),
+    ("search_prev", Action::SearchPrev, "Previous match"),
+    ("cancel", Action::Cancel, "Cancel"),
+    ("confirm", Action::Confirm, "Confirm"),
+    ("bookmarks_menu", Action::BookmarksMenu, "Bookmarks"),
+    ("delete_menu", Action::DeleteMenu, "Delete"),
+    ("exit_menu", Action::ExitMenu, "Exit"),
+    ("bookmark_list", Action::BookmarkList, "Bookmark list"),
+    ("bookmark_add", Action::BookmarkAdd, "Add bookmark"),
+    ("bookmark_delete", Action::BookmarkDelete, "Delete bookmark"),
+    ("command_line", Action::CommandLine, "Command line"),
+    ("complete", Action::Complete, "Complete"),
+    ("complete_prev", Action::CompletePrev, "Previous completion"),
+    ("go_top", Action::GoTop, "Go to first entry"),
+    ("go_bottom", Action::GoBottom, "Go to last entry"),
+    ("go_home", Action::GoHome, "Go home"),
+    ("go_root", Action::GoRoot, "Go to root"),
+    ("open_with", Action::OpenWith, "Open with…"),
+    ("jump_back", Action::JumpBack, "Jump back"),
+    ("jump_forward", Action::JumpForward, "Jump forward"),
+    ("jumps", Action::Jumps, "Jump list"),
+    ("new_tab", Action::NewTab, "New tab"),
+    ("close_tab", Action::CloseTab, "Close tab"),
+    ("next_tab", Action::NextTab, "Next tab"),
+    ("prev_tab", Action::PrevTab, "Previous tab"),
+    ("move_tab_left", Action::MoveTabLeft, "Move tab left"),
+    ("move_tab_right", Action::MoveTabRight, "Move tab right"),
+    (
+        "toggle_dual_pane",
+        Action::ToggleDualPane,
+        "Toggle dual-pane layout",
+    ),
+    ("switch_pane", Action::SwitchPane, "Focus other pane"),
+    ("copy_to_pane", Action::CopyToPane, "Copy to other pane"),
+    ("move_to_pane", Action::MoveToPane, "Move to other pane"),
+    ("goto", Action::GoTo, "Go to path"),
+    ("finder", Action::Finder, "Fuzzy find"),
+    ("z_jump", Action::ZJump, "Jump to a frecent directory"),
+    ("choose", Action::Choose, "Pick the marked files"),
+    ("filter", Action::Filter, "Filter entries"),
+    ("clear_filter", Action::ClearFilter, "Clear filter"),
+    ("grep", Action::Grep, "Search file contents"),
+];
+
+impl Action {
+    pub fn from_name(name: &str) -> Option<Self> {
+        ACTIONS
+            .iter()
+            .find(|(action_name, _, _)| *action_name == name)
+            .map(|(_, action, _)| *action)
+    }
+
+    pub fn name(&self) -> &'static str {
+        ACTIONS
+            .iter()
+            .find(|(_, action, _)| action == self)
+            .map(|(name, _, _)| *name)
+            .unwrap_or("unknown")
+    }
+
+    pub fn description(&self) -> &'static str {
+        ACTIONS
+            .iter()
+            .find(|(_, action, _)| action == self)
+            .map(|(_, _, description)| *description)
+            .unwrap_or("")
+    }
+}
+
+const DEFAULT_KEYS: [(KeymapMode, &[(&str, Action)]); 12] = [
+    (
+        KeymapMode::Normal,
+        &[
+            ("q", Action::Quit),
+            ("k", Action::MoveUp),
+            ("<Up>", Action::MoveUp),
+            ("j", Action::MoveDown),
+            ("<Down>", Action::MoveDown),
+            ("<C-u>", Action::PageUp),
+            ("<C-d>", Action::PageDown),
+            ("h", Action::Parent),
+            ("<Left>", Action::Parent),
+            ("<C-h>", Action::ToggleHidden),
+            ("l", Action::Open),
+            ("<Right>", Action::Open),
+            ("<Enter>", Action::Choose),
+            ("o", Action::OpenWith),
+            ("r", Action::Rename),
+            ("a", Action::Add),
+            ("y", Action::Yank),
+            ("p", Action::Paste),
+            ("v", Action::ToggleVisual),
+            ("<Space>", Action::ToggleMark),
+            ("b", Action::BookmarksMenu),
+            ("z", Action::ExitMenu),
+            ("Z", Action::ExitMenu),
+            ("d", Action::DeleteMenu),
+            ("/", Action::Search),
+            ("f", Action::Filter),
+            ("F", Action::ClearFilter),
+            (":", Action::CommandLine),
+            ("gg", Action::GoTop),
+            ("G", Action::GoBottom),
+            ("gh", Action::GoHome),
+            ("gr", Action::GoRoot),
+            ("gp", Action::GoTo),
+            ("<C-f>", Action::Finder),
+            ("gz", Action::ZJump),
+            ("g/", Action::Grep),
+            ("<C-o>", Action::JumpBack),
+            // most terminals send Tab for Ctrl-i
+            ("<C-i>", Action::JumpForward),
+            ("<Tab>", Action::JumpForward),
+            ("<C-t>", Action::NewTab),
+            ("<C-w>", Action::CloseTab),
+            ("gt", Action::NextTab),
+            ("gT", Action::PrevTab),
+            ("g<lt>", Action::MoveTabLeft),
+            ("g>", Action::MoveTabRight),
+            ("W", Action::ToggleDualPane),
+            ("w", Action::SwitchPane),
+            ("<F5>", Action::CopyToPane),
+            ("<F6>", Action::MoveToPane),
+            ("n", Action::SearchNext),
+            ("N", Action::SearchPrev),
+            ("<Esc>", Action::Cancel),
+        ],
+    ),
+    (
+        KeymapMode::Visual,
+        &[
+            ("k", Action::MoveUp),
+            ("<Up>", Action::MoveUp),
+            ("j", Action::MoveDown),
+            ("<Down>", Action::MoveDown),
+            ("gg", Action::GoTop),
+            ("G", Action::GoBottom),
+            ("v", Action::ToggleVisual),
+            ("o", Action::OpenWith),
+            ("<Enter>", Action::Choose),
+            ("<F5>", Action::CopyToPane),
+            ("<F6>", Action::MoveToPane),
+            ("<C-[>", Action::Cancel),
+            ("<Esc>", Action::Cancel),
+        ],
+    ),
+    (
+        KeymapMode::Insert,
+        &[("<Enter>", Action::Confirm), ("<Esc>", Action::Cancel)],
+    ),
+    (
+        KeymapMode::CommandLine,
+        &[
+            ("<Enter>", Action::Confirm),
+            ("<Esc>", Action::Cancel),
+            ("<Tab>", Action::Complete),
+            ("<S-Tab>", Action::CompletePrev),
+        ],
+    ),
+    (
+        KeymapMode::Bookmarks,
+        &[
+            ("q", Action::Cancel),
+            ("<Esc>", Action::Cancel),
+            ("k", Action::MoveUp),
+            ("<Up>", Action::MoveUp),
+            ("j", Action::MoveDown),
+            ("<Down>", Action::MoveDown),
+            ("d", Action::BookmarkDelete),
+            ("l", Action::Open),
+            ("<Enter>", Action::Open),
+        ],
+    ),
+    (
+        KeymapMode::BookmarksMenu,
+        &[
+            ("b", Action::BookmarkList),
+            ("a", Action::BookmarkAdd),
+            ("q", Action::Cancel),
+            ("<Esc>", Action::Cancel),
+        ],
+    ),
+    (
+        KeymapMode::DeleteMenu,
+        &[
+            ("d", Action::Cut),
+            ("D", Action::Trash),
+            ("x", Action::Delete),
+            ("q", Action::Cancel),
+            ("<Esc>", Action::Cancel),
+        ],
+    ),
+    (
+        KeymapMode::ExitMenu,
+        &[
+            ("Z", Action::QuitToCurrentDir),
+            ("z", Action::QuitToCurrentDir),
+            ("Q", Action::Quit),
+            ("q", Action::Quit),
+            ("<Esc>", Action::Cancel),
+        ],
+    ),
+    (
+        KeymapMode::OpenWith,
+        &[
+            ("k", Action::MoveUp),
+            ("<Up>", Action::MoveUp),
+            ("j", Action::MoveDown),
+            ("<Down>", Action::MoveDown),
+            ("l", Action::Confirm),
+            ("<Enter>", Action::Confirm),
+            ("q", Action::Cancel),
+            ("<Esc>", Action::Cancel),
+        ],
+    ),
+    (
+        KeymapMode::Jumps,
+        &[
+            ("k", Action::MoveUp),
+            ("<Up>", Action::MoveUp),
+            ("j", Action::MoveDown),
+            ("<Down>", Action::MoveDown),
+            ("l", Action::Confirm),
+            ("<Enter>", Action::Confirm),
+            ("q", Action::Cancel),
+            ("<Esc>", Action::Cancel),
+        ],
+    ),
+    (
+        KeymapMode::Finder,
+        &[
+            ("<Up>", Action::MoveUp),
+            ("<C-p>", Action::MoveUp),
+            ("<C-k>", Action::MoveUp),
+            ("<Down>", Action::MoveDown),
+            ("<C-n>", Action::MoveDown),
+            ("<C-j>", Action::MoveDown),
+            ("<Enter>", Action::Confirm),
+            ("<Esc>", Action::Cancel),
+        ],
+    ),
+    (
+        KeymapMode::Grep,
+        &[
+            ("k", Action::MoveUp),
+            ("<Up>", Action::MoveUp),
+            ("j", Action::MoveDown),
+            ("<Down>", Action::MoveDown),
+            ("<C-u>", Action::PageUp),
+            ("<C-d>", Action::PageDown),
+            ("l", Action::Confirm),
+            ("<Enter>", Action::Confirm),
+            ("q", Action::Cancel),
+            ("<Esc>", Action::Cancel),
+        ],
+    ),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // the char itself already carries the shift, so `N` and `<S-n>` are the same chord
+        let (code, modifiers) = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => (
+                KeyCode::Char(c.to_ascii_uppercase()),
+                modifiers - KeyModifiers::SHIFT,
+            ),
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                (KeyCode::BackTab, modifiers - KeyModifiers::SHIFT)
+            }
+            KeyCode::BackTab => (KeyCode::BackTab, modifiers - KeyModifiers::SHIFT),
+            _ => (code, modifiers),
+        };
+        let modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        KeyChord { code, modifiers }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let Some(inner) = value
+            .strip_prefix('<')
+            .and_then(|v| v.strip_suffix('>'))
+            .filter(|v| !v.is_empty())
+        else {
+            let mut chars = value.chars();
+            return match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(KeyChord::new(KeyCode::Char(c), KeyModifiers::NONE)),
+                _ => Err(format!("invalid key \"{}\"", value)),
+            };
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = inner;
+        while rest.len() > 2 && rest.as_bytes()[1] == b'-' {
+            modifiers |= match rest.as_bytes()[0].to_ascii_uppercase() {
+                b'C' => KeyModifiers::CONTROL,
+                b'A' | b'M' => KeyModifiers::ALT,
+                b'S' => KeyModifiers::SHIFT,
+                _ => return Err(format!("invalid modifier in key \"{}\"", value)),
+            };
+            rest = &rest[2..];
+        }
+
+        let code = match rest.to_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "esc" => KeyCode::Esc,
+            "enter" | "cr" | "return" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "bs" | "backspace" => KeyCode::Backspace,
+            "del" | "delete" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "lt" => KeyCode::Char('<'),
+            "gt" => KeyCode::Char('>'),
+            name => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                        _ => return Err(format!("invalid key \"{}\"", value)),
+                    },
+                }
+            }
+        };
+
+        Ok(KeyChord::new(code, modifiers))
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(key: KeyEvent) -> Self {
+        KeyChord::new(key.code, key.modifiers)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char('<') => "lt".to_string(),
+            KeyCode::Char('>') => "gt".to_string(),
+            KeyCode::Char(c) if self.modifiers.is_empty() => return write!(f, "{}", c),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "S-Tab".to_string(),
+            KeyCode::Backspace => "BS".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Insert => "Insert".to_string(),
+            KeyCode::Up => "Up".to_string(),
+            KeyCode::Down => "Down".to_string(),
+            KeyCode::Left => "Left".to_string(),
+            KeyCode::Right => "Right".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PageUp".to_string(),
+            KeyCode::PageDown => "PageDown".to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            code => format!("{:?}", code),
+        };
+
+        let mut prefix = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            prefix.push_str("C-");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            prefix.push_str("A-");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            prefix.push_str("S-");
+        }
+        write!(f, "<{}{}>", prefix, name)
+    }
+}
+
+/// Raw `[keys]` section: per mode, an action name mapped to the keys that trigger it.
+/// Listing an action replaces its default keys in that mode.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeysConfig {
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub normal: IndexMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub visual: IndexMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub insert: IndexMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub command_line: IndexMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub bookmarks: IndexMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub bookmarks_menu: IndexMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub delete_menu: IndexMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub exit_menu: IndexMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub open_with: IndexMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub jumps: IndexMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub finder: IndexMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub grep: IndexMap<String, Vec<String>>,
+}
+
+impl KeysConfig {
+    fn for_mode(&self, mode: KeymapMode) -> &IndexMap<String, Vec<String>> {
+        match mode {
+            KeymapMode::Normal => &self.normal,
+            KeymapMode::Visual => &self.visual,
+            KeymapMode::Insert => &self.insert,
+            KeymapMode::CommandLine => &self.command_line,
+            KeymapMode::Bookmarks => &self.bookmarks,
+            KeymapMode::BookmarksMenu => &self.bookmarks_menu,
+            KeymapMode::DeleteMenu => &self.delete_menu,
+            KeymapMode::ExitMenu => &self.exit_menu,
+            KeymapMode::OpenWith => &self.open_with,
+            KeymapMode::Jumps => &self.jumps,
+            KeymapMode::Finder => &self.finder,
+            KeymapMode::Grep => &self.grep,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct KeySequence(pub Vec<KeyChord>);
+
+impl KeySequence {
+    /// Parses a run of keys such as `gg`, `<C-w>j` or `<Space>`.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut chords = Vec::new();
+        let mut rest = value;
+        while let Some(c) = rest.chars().next() {
+            let len = match rest.find('>') {
+                Some(end) if c == '<' && end > 1 => end + 1,
+                _ => c.len_utf8(),
+            };
+            chords.push(KeyChord::parse(&rest[..len])?);
+            rest = &rest[len..];
+        }
+        if chords.is_empty() {
+            return Err("empty key".to_string());
+        }
+        Ok(KeySequence(chords))
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chord in &self.0 {
+            write!(f, "{}", chord)?;
+        }
+        Ok(())
+    }
+}
+
+/// Keys typed so far that have not resolved to an action yet, plus the count prefix.
+#[derive(Debug, Default)]
+pub struct PendingKeys {
+    pub count: Option<usize>,
+    pub keys: Vec<KeyChord>,
+    since: Option<Instant>,
+}
+
+impl PendingKeys {
+    pub fn is_empty(&self) -> bool {
+        self.count.is_none() && self.keys.is_empty()
+    }
+
+    pub fn push_digit(&mut self, digit: usize) {
+        let count = self.count.unwrap_or(0).saturating_mul(10);
+        self.count = Some(count.saturating_add(digit));
+    }
+
+    pub fn push_key(&mut self, key: KeyChord) {
+        self.keys.push(key);
+        self.since = Some(Instant::now());
+    }
+
+    pub fn take_count(&mut self) -> Option<usize> {
+        self.count.take()
+    }
+
+    pub fn clear_keys(&mut self) {
+        self.keys.clear();
+        self.since = None;
+    }
+
+    pub fn clear(&mut self) {
+        self.count = None;
+        self.clear_keys();
+    }
+
+    pub fn is_expired(&self, timeout: Duration) -> bool {
+        self.since.is_some_and(|since| since.elapsed() >= timeout)
+    }
+}
+
+impl fmt::Display for PendingKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(count) = self.count {
+            write!(f, "{}", count)?;
+        }
+        for chord in &self.keys {
+            write!(f, "{}", chord)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum KeyLookup {
+    /// The keys are bound and nothing longer starts with them.
+    Action(Action),
+    /// The keys are a prefix of longer bindings; `exact` fires if the sequence times out.
+    Pending {
+        exact: Option<Action>,
+    },
+    None,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Keymap {
+    bindings: IndexMap<KeymapMode, IndexMap<KeySequence, Action>>,
+}
+
+impl Keymap {
+    pub fn from_config(keys: &KeysConfig) -> io::Result<Self> {
+        let mut bindings = IndexMap::new();
+
+        for (mode, defaults) in DEFAULT_KEYS {
+            let user_keys = keys.for_mode(mode);
+            let mut user_bindings: IndexMap<KeySequence, Action> = IndexMap::new();
+
+            for (action_name, sequences) in user_keys {
+                let action = Action::from_name(action_name).ok_or_else(|| {
+                    invalid_keys(format!(
+                        "keys.{}: unknown action \"{}\"",
+                        mode.name(),
+                        action_name
+                    ))
+                })?;
+                for sequence in sequences {
+                    let key = KeySequence::parse(sequence).map_err(|e| {
+                        invalid_keys(format!("keys.{}.{}: {}", mode.name(), action_name, e))
+                    })?;
+                    if let Some(other) = user_bindings.insert(key.clone(), action) {
+                        if other != action {
+                            return Err(invalid_keys(format!(
+                                "keys.{}: \"{}\" is bound to both \"{}\" and \"{}\"",
+                                mode.name(),
+                                key,
+                                other.name(),
+                                action.name()
+                            )));
+                        }
+                    }
+                }
+            }
+
+            let mut mode_bindings: IndexMap<KeySequence, Action> = IndexMap::new();
+            for (sequence, action) in defaults.iter() {
+                let overridden = user_keys.contains_key(action.name());
+                let key = KeySequence::parse(sequence).map_err(invalid_keys)?;
+                if !overridden && !user_bindings.contains_key(&key) {
+                    mode_bindings.insert(key, *action);
+                }
+            }
+            mode_bindings.extend(user_bindings);
+
+            bindings.insert(mode, mode_bindings);
+        }
+
+        Ok(Keymap { bindings })
+    }
+
+    pub fn lookup(&self, mode: KeymapMode, keys: &[KeyChord]) -> KeyLookup {
+        let Some(bindings) = self.bindings.get(&mode) else {
+            return KeyLookup::None;
+        };
+        let mut exact = None;
+        let mut has_continuation = false;
+        for (sequence, action) in bindings {
+            if sequence.0 == keys {
+                exact = Some(*action);
+            } else if sequence.0.starts_with(keys) {
+                has_continuation = true;
+            }
+        }
+        match (exact, has_continuation) {
+            (_, true) => KeyLookup::Pending { exact },
+            (Some(action), false) => KeyLookup::Action(action),
+            (None, false) => KeyLookup::None,
+        }
+    }
+
+    /// Bindings that continue the typed keys, with the typed part stripped.
+    pub fn continuations(&self, mode: KeymapMode, keys: &[KeyChord]) -> Vec<(KeySequence, Action)> {
+        self.bindings
+            .get(&mode)
+            .map(|b| {
+                b.iter()
+                    .filter(|(sequence, _)| {
+                        sequence.0.len() > keys.len() && sequence.0.starts_with(keys)
+                    })
+                    .map(|(sequence, action)| {
+                        (KeySequence(sequence.0[keys.len()..].to_vec()), *action)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn keys_for(&self, mode: KeymapMode, action: Action) -> Vec<KeySequence> {
+        self.bindings
+            .get(&mode)
+            .map(|b| {
+                b.iter()
+                    .filter(|(_, a)| **a == action)
+                    .map(|(k, _)| k.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn invalid_keys(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyChord {
+        KeyChord::from(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn parse_chords() {
+        assert_eq!(
+            KeyChord::parse("j").unwrap(),
+            KeyChord::new(KeyCode::Char('j'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            KeyChord::parse("<C-d>").unwrap(),
+            KeyChord::new(KeyCode::Char('d'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            KeyChord::parse("<S-n>").unwrap(),
+            KeyChord::parse("N").unwrap()
+        );
+        assert_eq!(
+            KeyChord::parse("<space>").unwrap(),
+            KeyChord::new(KeyCode::Char(' '), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            KeyChord::parse("<F5>").unwrap(),
+            KeyChord::new(KeyCode::F(5), KeyModifiers::NONE)
+        );
+        assert!(KeyChord::parse("jj").is_err());
+        assert!(KeyChord::parse("<X-j>").is_err());
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for value in [
+            "j",
+            "N",
+            "<C-d>",
+            "<Space>",
+            "<Esc>",
+            "<A-Enter>",
+            "<S-Tab>",
+        ] {
+            let chord = KeyChord::parse(value).unwrap();
+            assert_eq!(KeyChord::parse(&chord.to_string()).unwrap(), chord);
+        }
+    }
+
+    #[test]
+    fn default_bindings() {
+        let keymap = Keymap::from_config(&KeysConfig::default()).unwrap();
+        assert_eq!(
+            keymap.lookup(
+                KeymapMode::Normal,
+                &[key(KeyCode::Char('j'), KeyModifiers::NONE)]
+            ),
+            KeyLookup::Action(Action::MoveDown)
+        );
+        assert_eq!(
+            keymap.lookup(
+                KeymapMode::Normal,
+                &[key(KeyCode::Char('N'), KeyModifiers::SHIFT)]
+            ),
+            KeyLookup::Action(Action::SearchPrev)
+        );
+        assert_eq!(
+            keymap.lookup(
+                KeymapMode::DeleteMenu,
+                &[key(KeyCode::Char('x'), KeyModifiers::NONE)]
+            ),
+            KeyLookup::Action(Action::Delete)
+        );
+    }
+
+    #[test]
+    fn tab_completes_only_in_command_prompts() {
+        let keymap = Keymap::from_config(&KeysConfig::default()).unwrap();
+        let tab = [key(KeyCode::Tab, KeyModifiers::NONE)];
+        let command = ModalKind::BottomLine {
+            action: BottomLineModalAction::Command,
+        };
+        let search = ModalKind::BottomLine {
+            action: BottomLineModalAction::Search,
+        };
+
+        let mode = KeymapMode::from_state(&Mode::Insert, &command);
+        assert_eq!(mode, KeymapMode::CommandLine);
+        assert_eq!(
+            keymap.lookup(mode, &tab),
+            KeyLookup::Action(Action::Complete)
+        );
+        let mode = KeymapMode::from_state(&Mode::Insert, &search);
+        assert_eq!(mode, KeymapMode::Insert);
+        assert_eq!(keymap.lookup(mode, &tab), KeyLookup::None);
+    }
+
+    #[test]
+    fn user_bindings_replace_defaults() {
+        let mut keys = KeysConfig::default();
+        keys.normal.insert("move_down".into(), vec!["n".into()]);
+        keys.normal
+            .insert("delete_menu".into(), vec!["<C-x>".into()]);
+        let keymap = Keymap::from_config(&keys).unwrap();
+
+        let normal = |code| keymap.lookup(KeymapMode::Normal, &[key(code, KeyModifiers::NONE)]);
+        assert_eq!(
+            normal(KeyCode::Char('n')),
+            KeyLookup::Action(Action::MoveDown)
+        );
+        assert_eq!(normal(KeyCode::Char('j')), KeyLookup::None);
+        assert_eq!(normal(KeyCode::Char('d')), KeyLookup::None);
+        assert_eq!(
+            keymap.lookup(
+                KeymapMode::Normal,
+                &[key(KeyCode::Char('x'), KeyModifiers::CONTROL)]
+            ),
+            KeyLookup::Action(Action::DeleteMenu)
+        );
+    }
+
+    #[test]
+    fn unknown_action_is_an_error() {
+        let mut keys = KeysConfig::default();
+        keys.normal.insert("move_dwn".into(), vec!["n".into()]);
+        let err = Keymap::from_config(&keys).unwrap_err();
+        assert!(err.to_string().contains("unknown action \"move_dwn\""));
+    }
+
+    #[test]
+    fn conflicting_bindings_are_an_error() {
+        let mut keys = KeysConfig::default();
+        keys.normal.insert("move_down".into(), vec!["<C-n>".into()]);
+        keys.normal
+            .insert("search_next".into(), vec!["<c-n>".into()]);
+        let err = Keymap::from_config(&keys).unwrap_err();
+        assert!(err.to_string().contains("bound to both"));
+    }
+
+    #[test]
+    fn parse_sequences() {
+        let sequence = KeySequence::parse("<C-w>j").unwrap();
+        assert_eq!(
+            sequence.0,
+            vec![
+                KeyChord::new(KeyCode::Char('w'), KeyModifiers::CONTROL),
+                KeyChord::new(KeyCode::Char('j'), KeyModifiers::NONE),
+            ]
+        );
+        assert_eq!(KeySequence::parse("gg").unwrap().to_string(), "gg");
+        assert_eq!(KeySequence::parse("<").unwrap().0.len(), 1);
+        assert!(KeySequence::parse("").is_err());
+    }
+
+    #[test]
+    fn sequences_wait_for_continuation() {
+        let keymap = Keymap::from_config(&KeysConfig::default()).unwrap();
+        let g = key(KeyCode::Char('g'), KeyModifiers::NONE);
+        let h = key(KeyCode::Char('h'), KeyModifiers::NONE);
+
+        assert_eq!(
+            keymap.lookup(KeymapMode::Normal, &[g]),
+            KeyLookup::Pending { exact: None }
+        );
+        assert_eq!(
+            keymap.lookup(KeymapMode::Normal, &[g, g]),
+            KeyLookup::Action(Action::GoTop)
+        );
+        assert_eq!(
+            keymap.lookup(KeymapMode::Normal, &[g, h]),
+            KeyLookup::Action(Action::GoHome)
+        );
+
+        let hints: Vec<String> = keymap
+            .continuations(KeymapMode::Normal, &[g])
+            .iter()
+            .map(|(rest, _)| rest.to_string())
+            .collect();
+        assert_eq!(
+            hints,
+            vec!["g", "h", "r", "p", "z", "/", "t", "T", "<lt>", "<gt>"]
+        );
+    }
+
+    #[test]
+    fn prefix_binding_is_pending_with_exact_match() {
+        let mut keys = KeysConfig::default();
+        keys.normal.insert("go_root".into(), vec!["g".into()]);
+        let keymap = Keymap::from_config(&keys).unwrap();
+        let g = key(KeyCode::Char('g'), KeyModifiers::NONE);
+
+        assert_eq!(
+            keymap.lookup(KeymapMode::Normal, &[g]),
+            KeyLookup::Pending {
+                exact: Some(Action::GoRoot)
+            }
+        );
+    }
+
+    #[test]
+    fn pending_keys_show_count_and_keys() {
+        let mut pending = PendingKeys::default();
+        pending.push_digit(1);
+        pending.push_digit(2);
+        pending.push_key(key(KeyCode::Char('g'), KeyModifiers::NONE));
+        assert_eq!(pending.to_string(), "12g");
+        assert_eq!(pending.take_count(), Some(12));
+        pending.clear();
+        assert!(pending.is_empty());
+    }
+}
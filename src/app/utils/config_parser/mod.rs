@@ -1,59 +1,308 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use std::ffi::OsString;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::app::utils::config_parser::default_config::Config;
+use crate::app::utils::config_parser::{default_config::Config, shell_init::Shell};
 
 pub mod default_config;
+pub mod keymap;
+pub mod opener;
+pub mod shell_init;
+pub mod theme;
 
-#[derive(Parser, Debug)]
-struct Args {
+#[derive(Parser, Debug, Default)]
+pub struct Args {
     #[arg(long)]
-    editor: Option<String>,
-    #[arg(long, default_value = "config.toml")]
-    config_path: String,
-}
-
-pub fn load_config() -> Config {
-    let args = Args::parse();
-    let config_path = &args.config_path;
-
-    let mut config = Config::default();
-
-    if Path::new(config_path).exists() {
-        match fs::read_to_string(config_path) {
-            Ok(config_content) => match toml::from_str::<Config>(&config_content) {
-                Ok(file_config) => {
-                    config.common.editor = file_config.common.editor;
-                    config.bookmarks = file_config.bookmarks;
-                }
-                Err(e) => {
-                    eprintln!("Failed to parse config file '{}': {}", config_path, e);
-                }
-            },
-            Err(e) => {
-                eprintln!("Failed to read config file '{}': {}", config_path, e);
-            }
-        }
+    pub editor: Option<String>,
+    /// Config file to use instead of the discovered one
+    #[arg(long)]
+    pub config_path: Option<PathBuf>,
+    /// Print the resolved config file path and exit
+    #[arg(long)]
+    pub print_config_path: bool,
+    /// Pick a file: opening one writes its path to PATH (`-` for stdout) and quits
+    #[arg(long, value_name = "PATH")]
+    pub choosefile: Option<PathBuf>,
+    /// Like --choosefile, but writes every marked file
+    #[arg(long, value_name = "PATH")]
+    pub choosefiles: Option<PathBuf>,
+    /// Write the directory stranger was in on exit to PATH (`-` for stdout)
+    #[arg(long, value_name = "PATH")]
+    pub choosedir: Option<PathBuf>,
+    /// Terminate chosen paths with NUL instead of a newline
+    #[arg(long)]
+    pub print0: bool,
+    /// Start with PATH selected in its directory
+    #[arg(long, value_name = "PATH")]
+    pub selectfile: Option<PathBuf>,
+    /// On exit with `Z`, write the current directory to PATH for a shell wrapper to `cd` into
+    #[arg(long, value_name = "PATH")]
+    pub last_dir_file: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Print a shell function that runs stranger and `cd`s to where it exited with `Z`
+    ShellInit { shell: Shell },
+}
+
+/// Resolves the config file: `--config-path`, then `$STRANGER_CONFIG`,
+/// then `$XDG_CONFIG_HOME/stranger/config.toml`, then `~/.config/stranger/config.toml`.
+fn resolve_config_path(
+    cli_path: Option<PathBuf>,
+    env: impl Fn(&str) -> Option<OsString>,
+) -> Option<PathBuf> {
+    let non_empty = |key: &str| env(key).filter(|value| !value.is_empty());
+
+    if let Some(path) = cli_path {
+        return Some(path);
     }
+    if let Some(path) = non_empty("STRANGER_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+
+    let xdg_config_home = non_empty("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute());
+    let config_home = xdg_config_home
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+    config_home.map(|dir| dir.join("stranger").join("config.toml"))
+}
+
+/// Per-user app directory: `$<xdg_var>/stranger`, then `~/<home_fallback>/stranger`.
+fn resolve_app_dir(
+    env: impl Fn(&str) -> Option<OsString>,
+    xdg_var: &str,
+    home_fallback: &str,
+) -> Option<PathBuf> {
+    let non_empty = |key: &str| env(key).filter(|value| !value.is_empty());
 
-    if let Some(editor_arg) = args.editor {
-        config.common.editor = editor_arg;
+    let xdg_home = non_empty(xdg_var)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute());
+    let home =
+        xdg_home.or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(home_fallback)));
+
+    home.map(|dir| dir.join("stranger"))
+}
+
+/// Directory for settings the app writes itself: `$XDG_STATE_HOME/stranger`,
+/// then `~/.local/state/stranger`.
+pub fn state_dir() -> Option<PathBuf> {
+    resolve_app_dir(
+        |key| std::env::var_os(key),
+        "XDG_STATE_HOME",
+        ".local/state",
+    )
+}
+
+/// Directory for the data the app collects: `$XDG_DATA_HOME/stranger`,
+/// then `~/.local/share/stranger`.
+pub fn data_dir() -> Option<PathBuf> {
+    resolve_app_dir(|key| std::env::var_os(key), "XDG_DATA_HOME", ".local/share")
+}
+
+/// The config file `args` point to, found the same way `load_config` does.
+pub fn config_path(args: &Args) -> Option<PathBuf> {
+    resolve_config_path(args.config_path.clone(), |key| std::env::var_os(key))
+}
+
+pub fn load_config(args: &Args) -> (Config, Option<String>) {
+    let config = Config {
+        path: config_path(args),
+        editor_override: args.editor.clone(),
+        ..Config::default()
+    };
+
+    match reload_config(&config) {
+        Ok(file_config) => (file_config, None),
+        Err(e) => (config, Some(e)),
     }
+}
 
-    config
+pub fn read_config(config_path: &Path) -> Result<Config, String> {
+    let config_content = fs::read_to_string(config_path).map_err(|e| {
+        format!(
+            "Failed to read config file '{}': {}",
+            config_path.display(),
+            e
+        )
+    })?;
+    toml::from_str::<Config>(&config_content).map_err(|e| {
+        // single line, the notification bar has no room for the toml snippet
+        let line = e
+            .span()
+            .map(|span| config_content[..span.start].matches('\n').count() + 1)
+            .unwrap_or(1);
+        format!(
+            "Failed to parse config file '{}' (line {}): {}",
+            config_path.display(),
+            line,
+            e.message()
+        )
+    })
+}
+
+/// Re-reads the config file on top of `current`, keeping its path and CLI overrides.
+pub fn reload_config(current: &Config) -> Result<Config, String> {
+    let mut config = current.clone();
+    if let Some(config_path) = current.path.as_ref().filter(|path| path.exists()) {
+        let file_config = read_config(config_path)?;
+        config.common = file_config.common;
+        config.bookmarks = file_config.bookmarks;
+        config.keys = file_config.keys;
+        config.theme = file_config.theme;
+        config.preview = file_config.preview;
+        config.openers = file_config.openers;
+    }
+    Ok(config)
 }
 
 pub fn save_config(config: &Config) -> std::io::Result<()> {
-    let args = Args::parse();
-    let config_path = &args.config_path;
+    let Some(config_path) = &config.path else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Failed to resolve config path",
+        ));
+    };
+
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
 
     let toml_string = toml::to_string(config).expect("Failed to serialize config to TOML");
     match fs::write(config_path, toml_string) {
         Ok(_) => Ok(()),
         Err(e) => {
-            eprintln!("Failed to update config file '{}': {}", config_path, e);
+            eprintln!(
+                "Failed to update config file '{}': {}",
+                config_path.display(),
+                e
+            );
             Err(e)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn env_from<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<OsString> + 'a {
+        let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+        move |key| vars.get(key).map(OsString::from)
+    }
+
+    #[test]
+    fn cli_path_wins() {
+        let env = env_from(&[("STRANGER_CONFIG", "/env/config.toml")]);
+        let path = resolve_config_path(Some(PathBuf::from("/cli/config.toml")), env);
+        assert_eq!(path, Some(PathBuf::from("/cli/config.toml")));
+    }
+
+    #[test]
+    fn env_override_before_xdg() {
+        let env = env_from(&[
+            ("STRANGER_CONFIG", "/env/config.toml"),
+            ("XDG_CONFIG_HOME", "/xdg"),
+        ]);
+        let path = resolve_config_path(None, env);
+        assert_eq!(path, Some(PathBuf::from("/env/config.toml")));
+    }
+
+    #[test]
+    fn xdg_config_home() {
+        let env = env_from(&[("XDG_CONFIG_HOME", "/xdg"), ("HOME", "/home/user")]);
+        let path = resolve_config_path(None, env);
+        assert_eq!(path, Some(PathBuf::from("/xdg/stranger/config.toml")));
+    }
+
+    #[test]
+    fn falls_back_to_home_config() {
+        let env = env_from(&[("XDG_CONFIG_HOME", "relative"), ("HOME", "/home/user")]);
+        let path = resolve_config_path(None, env);
+        assert_eq!(
+            path,
+            Some(PathBuf::from("/home/user/.config/stranger/config.toml"))
+        );
+        assert_eq!(resolve_config_path(None, env_from(&[])), None);
+    }
+
+    #[test]
+    fn app_dirs_follow_xdg() {
+        let env = env_from(&[("XDG_STATE_HOME", "/xdg/state"), ("HOME", "/home/user")]);
+        assert_eq!(
+            resolve_app_dir(env, "XDG_STATE_HOME", ".local/state"),
+            Some(PathBuf::from("/xdg/state/stranger"))
+        );
+        let env = env_from(&[("HOME", "/home/user")]);
+        assert_eq!(
+            resolve_app_dir(env, "XDG_STATE_HOME", ".local/state"),
+            Some(PathBuf::from("/home/user/.local/state/stranger"))
+        );
+        let env = env_from(&[("XDG_DATA_HOME", "relative"), ("HOME", "/home/user")]);
+        assert_eq!(
+            resolve_app_dir(env, "XDG_DATA_HOME", ".local/share"),
+            Some(PathBuf::from("/home/user/.local/share/stranger"))
+        );
+    }
+
+    #[test]
+    fn reload_keeps_path_and_editor_override() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "[common]\neditor = \"vim\"\n[bookmarks]\nwork = \"/work\"\n",
+        )
+        .unwrap();
+        let current = Config {
+            path: Some(path.clone()),
+            editor_override: Some("hx".into()),
+            ..Config::default()
+        };
+
+        let config = reload_config(&current).unwrap();
+        assert_eq!(config.path, Some(path.clone()));
+        assert_eq!(config.editor(), "hx");
+
+        // the override is for this run only
+        save_config(&config).unwrap();
+        let saved = read_config(&path).unwrap();
+        assert_eq!(saved.common.editor, "vim");
+        assert_eq!(config.bookmarks.get("work"), Some(&PathBuf::from("/work")));
+    }
+
+    #[test]
+    fn reload_reports_parse_errors() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[common\n").unwrap();
+        let current = Config {
+            path: Some(path),
+            ..Config::default()
+        };
+
+        let err = reload_config(&current).unwrap_err();
+        assert!(err.starts_with("Failed to parse config file"));
+    }
+
+    #[test]
+    fn save_creates_config_dir() {
+        let dir = tempdir().unwrap();
+        let config = Config {
+            path: Some(dir.path().join("stranger").join("config.toml")),
+            ..Config::default()
+        };
+        save_config(&config).unwrap();
+        assert!(dir.path().join("stranger/config.toml").exists());
+    }
+}
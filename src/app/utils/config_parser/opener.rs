@@ -0,0 +1,318 @@
+use std::{
+    io,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use glob::Pattern;
+use serde::{Deserialize, Serialize};
+
+use crate::app::utils::{fs::is_binary_file, i18n::Lang, split_args};
+
+/// One `[[openers]]` entry. Every matcher that is set must match; a rule without
+/// matchers applies to every file.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OpenerRule {
+    /// Extensions without the dot, compared case-insensitively
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ext: Vec<String>,
+    /// Glob on the file name, e.g. `*.tar.*`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub glob: Option<String>,
+    /// MIME type guessed from the extension, globs allowed (`image/*`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executable: Option<bool>,
+    /// Whether the file reads as text: UTF-8 without NUL bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<bool>,
+    /// `{}` is replaced by the files, otherwise they are appended; `{editor}`
+    /// by the configured editor
+    pub command: String,
+    /// Terminal programs take over the screen until they exit; GUI ones do not.
+    #[serde(default = "default_terminal")]
+    pub terminal: bool,
+    /// Only for GUI programs: block until the program exits instead of detaching.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub wait: bool,
+}
+
+fn default_terminal() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Opener {
+    pub rule: OpenerRule,
+    glob: Option<Pattern>,
+    mime: Option<Pattern>,
+    argv: Vec<String>,
+}
+
+impl Opener {
+    fn from_rule(index: usize, rule: &OpenerRule) -> io::Result<Self> {
+        let pattern = |field: &str, value: &Option<String>| {
+            value
+                .as_deref()
+                .map(Pattern::new)
+                .transpose()
+                .map_err(|e| invalid_opener(index, format!("invalid {}: {}", field, e)))
+        };
+        let argv = split_args(&rule.command).map_err(|e| invalid_opener(index, e))?;
+        if argv.is_empty() {
+            return Err(invalid_opener(index, "empty command".to_string()));
+        }
+
+        Ok(Opener {
+            rule: rule.clone(),
+            glob: pattern("glob", &rule.glob)?,
+            mime: pattern("mime", &rule.mime)?,
+            argv,
+        })
+    }
+
+    /// Ad-hoc terminal command typed into the "Open with" prompt.
+    pub fn from_command(command: &str) -> Result<Self, String> {
+        let argv = split_args(command)?;
+        if argv.is_empty() {
+            return Err(Lang::en("empty_command").into());
+        }
+        let rule = OpenerRule {
+            ext: vec![],
+            glob: None,
+            mime: None,
+            executable: None,
+            text: None,
+            command: command.to_string(),
+            terminal: true,
+            wait: false,
+        };
+
+        Ok(Opener {
+            rule,
+            glob: None,
+            mime: None,
+            argv,
+        })
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+
+        let ext_matches = self.rule.ext.is_empty()
+            || path.extension().is_some_and(|ext| {
+                let ext = ext.to_string_lossy();
+                self.rule.ext.iter().any(|e| e.eq_ignore_ascii_case(&ext))
+            });
+        let glob_matches = self
+            .glob
+            .as_ref()
+            .is_none_or(|glob| glob.matches(&file_name));
+        let mime_matches = self.mime.as_ref().is_none_or(|mime| {
+            mime_guess::from_path(path)
+                .iter()
+                .any(|guess| mime.matches(guess.essence_str()))
+        });
+        let executable_matches = self
+            .rule
+            .executable
+            .is_none_or(|executable| is_executable(path) == executable);
+        // last, as it reads the file
+        let text_matches = || {
+            self.rule
+                .text
+                .is_none_or(|text| is_binary_file(path).is_ok_and(|binary| binary != text))
+        };
+
+        ext_matches && glob_matches && mime_matches && executable_matches && text_matches()
+    }
+
+    /// Program and arguments with the files substituted for `{}` and `editor`
+    /// for `{editor}`.
+    pub fn command(&self, files: &[PathBuf], editor: &str) -> (String, Vec<String>) {
+        let files: Vec<String> = files
+            .iter()
+            .map(|file| file.to_string_lossy().into_owned())
+            .collect();
+        let (program, template) = self.argv.split_first().expect("checked in from_rule");
+        let expand = |arg: &String| match arg.as_str() {
+            "{editor}" => editor.to_string(),
+            _ => arg.clone(),
+        };
+
+        let mut args = Vec::new();
+        let mut substituted = false;
+        for arg in template {
+            if arg == "{}" {
+                args.extend(files.iter().cloned());
+                substituted = true;
+            } else {
+                args.push(expand(arg));
+            }
+        }
+        if !substituted {
+            args.extend(files);
+        }
+        (expand(program), args)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Openers(Vec<Opener>);
+
+impl Openers {
+    pub fn from_config(rules: &[OpenerRule]) -> io::Result<Self> {
+        rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| Opener::from_rule(index, rule))
+            .collect::<io::Result<Vec<_>>>()
+            .map(Openers)
+    }
+
+    /// First rule matching the file, in config order.
+    pub fn find(&self, path: &Path) -> Option<&Opener> {
+        self.0.iter().find(|opener| opener.matches(path))
+    }
+
+    /// Every rule matching all of the files.
+    pub fn candidates(&self, paths: &[PathBuf]) -> Vec<&Opener> {
+        self.0
+            .iter()
+            .filter(|opener| paths.iter().all(|path| opener.matches(path)))
+            .collect()
+    }
+}
+
+fn is_executable(path: &Path) -> bool {
+    path.metadata()
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+fn invalid_opener(index: usize, msg: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("openers[{}]: {}", index, msg),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::utils::config_parser::default_config::Config;
+
+    fn openers(toml_str: &str) -> Openers {
+        #[derive(Deserialize)]
+        struct Rules {
+            openers: Vec<OpenerRule>,
+        }
+        let rules: Rules = toml::from_str(toml_str).unwrap();
+        Openers::from_config(&rules.openers).unwrap()
+    }
+
+    #[test]
+    fn matches_by_ext_glob_and_mime() {
+        let openers = openers(
+            r#"
+            [[openers]]
+            ext = ["PDF"]
+            command = "zathura"
+            terminal = false
+
+            [[openers]]
+            glob = "*.tar.*"
+            command = "tar tf {}"
+
+            [[openers]]
+            mime = "image/*"
+            command = "feh --scale {}"
+            terminal = false
+            wait = true
+            "#,
+        );
+
+        let command = |path: &str| {
+            openers
+                .find(Path::new(path))
+                .map(|o| o.rule.command.as_str())
+        };
+        assert_eq!(command("/docs/paper.pdf"), Some("zathura"));
+        assert_eq!(command("backup.tar.gz"), Some("tar tf {}"));
+        assert_eq!(command("photo.JPG"), Some("feh --scale {}"));
+        assert_eq!(command("notes.txt"), None);
+    }
+
+    #[test]
+    fn executable_rule() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("run");
+        std::fs::write(&script, "").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let plain = dir.path().join("data");
+        std::fs::write(&plain, "").unwrap();
+
+        let openers = openers("[[openers]]\nexecutable = true\ncommand = \"sh -c {}\"\n");
+        assert!(openers.find(&script).is_some());
+        assert!(openers.find(&plain).is_none());
+    }
+
+    #[test]
+    fn text_files_open_in_the_editor_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let text = dir.path().join("notes.md");
+        let binary = dir.path().join("image.png");
+        std::fs::write(&text, "r\u{e9}sum\u{e9} \u{2500}\n").unwrap();
+        std::fs::write(&binary, b"\x89PNG\r\n\x1a\n\0\0").unwrap();
+
+        let openers = Openers::from_config(&Config::default().openers).unwrap();
+        let opener = openers.find(&text).unwrap();
+        assert_eq!(
+            opener.command(std::slice::from_ref(&text), "vim"),
+            ("vim".to_string(), vec![text.to_string_lossy().into_owned()])
+        );
+        assert!(openers.find(&binary).is_none());
+        assert!(openers.find(&dir.path().join("missing")).is_none());
+    }
+
+    #[test]
+    fn substitutes_files() {
+        let openers = openers(
+            "[[openers]]\ncommand = \"diff -u {} --color\"\n[[openers]]\ncommand = \"less\"\n",
+        );
+        let files = [PathBuf::from("/a b"), PathBuf::from("/c")];
+
+        let (program, args) = openers.0[0].command(&files, "vi");
+        assert_eq!(program, "diff");
+        assert_eq!(args, ["-u", "/a b", "/c", "--color"]);
+        assert_eq!(openers.0[1].command(&files, "vi").1, ["/a b", "/c"]);
+        assert_eq!(openers.candidates(&files).len(), 2);
+    }
+
+    #[test]
+    fn invalid_rules_are_errors() {
+        let rule = OpenerRule {
+            ext: vec![],
+            glob: Some("[".into()),
+            mime: None,
+            executable: None,
+            text: None,
+            command: "x".into(),
+            terminal: true,
+            wait: false,
+        };
+        let err = Openers::from_config(std::slice::from_ref(&rule)).unwrap_err();
+        assert!(err.to_string().starts_with("openers[0]: invalid glob"));
+
+        let empty = OpenerRule {
+            glob: None,
+            command: " ".into(),
+            ..rule
+        };
+        assert!(Openers::from_config(&[empty]).is_err());
+    }
+}
@@ -0,0 +1,95 @@
+use clap::ValueEnum;
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Shared by bash and zsh; `ret` because zsh reserves `status`.
+const POSIX_WRAPPER: &str = r#"stranger() {
+    local tmp dir ret
+    tmp="$(mktemp -t stranger-lastdir.XXXXXX)" || return
+    command stranger --last-dir-file "$tmp" "$@"
+    ret=$?
+    dir="$(cat -- "$tmp")"
+    rm -f -- "$tmp"
+    if [ -n "$dir" ] && [ -d "$dir" ] && [ "$dir" != "$PWD" ]; then
+        cd -- "$dir" || return
+    fi
+    return $ret
+}
+"#;
+
+const FISH_WRAPPER: &str = r#"function stranger --wraps stranger --description 'stranger, then cd to where it exited with Z'
+    set -l tmp (mktemp -t stranger-lastdir.XXXXXX); or return
+    command stranger --last-dir-file $tmp $argv
+    set -l ret $status
+    set -l dir (cat -- $tmp)
+    rm -f -- $tmp
+    if test -n "$dir"; and test -d "$dir"; and test "$dir" != "$PWD"
+        cd -- $dir; or return
+    end
+    return $ret
+end
+"#;
+
+/// Function replacing `stranger` in the shell, so that leaving with `Z`
+/// moves the shell itself instead of starting a nested one.
+pub fn wrapper(shell: Shell) -> &'static str {
+    match shell {
+        Shell::Bash | Shell::Zsh => POSIX_WRAPPER,
+        Shell::Fish => FISH_WRAPPER,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, os::unix::fs::PermissionsExt, process::Command};
+
+    use super::*;
+
+    #[test]
+    fn bash_wrapper_changes_to_last_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        let target = root.join("target");
+        fs::create_dir(&target).unwrap();
+
+        // fake binary that writes its last dir like the real one does on `Z`
+        let bin = root.join("bin");
+        fs::create_dir(&bin).unwrap();
+        let fake = bin.join("stranger");
+        fs::write(
+            &fake,
+            format!(
+                "#!/bin/sh\n[ \"$1\" = --last-dir-file ] && printf %s '{}' > \"$2\"\n",
+                target.display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&fake, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let script = format!("{}stranger; pwd", wrapper(Shell::Bash));
+        let path = format!(
+            "{}:{}",
+            bin.display(),
+            std::env::var("PATH").unwrap_or_default()
+        );
+        let output = match Command::new("bash")
+            .args(["--norc", "-c", &script])
+            .current_dir(&root)
+            .env("PATH", path)
+            .output()
+        {
+            Ok(output) => output,
+            // no bash to run it with
+            Err(_) => return,
+        };
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            target.to_string_lossy()
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use std::{io, str::FromStr};
+
+use indexmap::IndexMap;
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use serde::{Deserialize, Serialize};
+
+/// Raw `[theme]` section: an optional preset plus per-slot overrides,
+/// e.g. `selected_file = { fg = "white", bg = "#303030", modifiers = ["bold"] }`.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ThemeConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    /// Colour entries from `$LS_COLORS` when it is set (default); `false` keeps the theme.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ls_colors: Option<bool>,
+    #[serde(flatten)]
+    pub styles: IndexMap<String, StyleConfig>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StyleConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fg: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modifiers: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub dir: Style,
+    pub file: Style,
+    pub selected_dir: Style,
+    pub selected_file: Style,
+    pub matched: Style,
+    pub marked: Style,
+    pub marked_selected: Style,
+    pub bookmark: Style,
+    pub bookmark_selected: Style,
+    pub header_user: Style,
+    pub header_path: Style,
+    pub header_file: Style,
+    pub header_filter: Style,
+    pub tab: Style,
+    pub tab_active: Style,
+    pub footer_perms: Style,
+    pub footer_mtime: Style,
+    pub footer_size: Style,
+    pub footer_matches: Style,
+    pub footer_pending: Style,
+    pub notification_info: Style,
+    pub notification_success: Style,
+    pub notification_warn: Style,
+    pub notification_error: Style,
+    pub modal_border: Style,
+    pub prompt: Style,
+    pub hint_key: Style,
+    pub hint_header: Style,
+    pub hint_text: Style,
+}
+
+const BLACK: Color = Color::Rgb(0, 0, 0);
+
+impl Theme {
+    pub fn dark() -> Self {
+        let bold = Style::default().bold();
+        Theme {
+            dir: bold.fg(Color::Blue),
+            file: bold.fg(Color::White),
+            selected_dir: bold.bg(Color::Blue).fg(BLACK),
+            selected_file: bold.bg(Color::White).fg(BLACK),
+            matched: bold.fg(Color::Red),
+            marked: Style::default().fg(Color::Yellow),
+            marked_selected: Style::default().bg(Color::Yellow).fg(BLACK),
+            bookmark: bold.fg(Color::Gray),
+            bookmark_selected: bold.bg(Color::LightCyan).fg(BLACK),
+            header_user: bold.fg(Color::Green),
+            header_path: bold.fg(Color::Blue),
+            header_file: bold,
+            header_filter: bold.fg(Color::Yellow),
+            tab: Style::default().fg(Color::Gray),
+            tab_active: bold.bg(Color::Blue).fg(BLACK),
+            footer_perms: bold.fg(Color::LightBlue),
+            footer_mtime: bold.fg(Color::White),
+            footer_size: bold.fg(Color::LightGreen),
+            footer_matches: bold.fg(Color::Red),
+            footer_pending: Style::default().fg(Color::White),
+            notification_info: Style::default().fg(Color::White),
+            notification_success: Style::default().fg(Color::LightGreen),
+            notification_warn: Style::default().fg(Color::LightYellow),
+            notification_error: Style::default().fg(Color::LightRed),
+            modal_border: bold.fg(Color::LightGreen),
+            prompt: bold.fg(Color::White),
+            hint_key: Style::default().fg(Color::Yellow),
+            hint_header: Style::default().fg(Color::Cyan),
+            hint_text: Style::default().fg(Color::White),
+        }
+    }
+
+    /// For light terminal backgrounds: default foreground instead of white,
+    /// dark selection bars and no yellow text.
+    pub fn light() -> Self {
+        let bold = Style::default().bold();
+        Theme {
+            dir: bold.fg(Color::Blue),
+            file: bold.fg(Color::Reset),
+            selected_dir: bold.bg(Color::Blue).fg(Color::White),
+            selected_file: bold.bg(Color::DarkGray).fg(Color::White),
+            matched: bold.fg(Color::Red),
+            marked: Style::default().fg(Color::Magenta),
+            marked_selected: Style::default().bg(Color::Magenta).fg(Color::White),
+            bookmark: bold.fg(Color::DarkGray),
+            bookmark_selected: bold.bg(Color::Cyan).fg(BLACK),
+            header_user: bold.fg(Color::Green),
+            header_path: bold.fg(Color::Blue),
+            header_file: bold,
+            header_filter: bold.fg(Color::Magenta),
+            tab: Style::default().fg(Color::DarkGray),
+            tab_active: bold.bg(Color::Blue).fg(Color::White),
+            footer_perms: bold.fg(Color::Blue),
+            footer_mtime: bold.fg(Color::Reset),
+            footer_size: bold.fg(Color::Green),
+            footer_matches: bold.fg(Color::Red),
+            footer_pending: Style::default().fg(Color::Reset),
+            notification_info: Style::default().fg(Color::Reset),
+            notification_success: Style::default().fg(Color::Green),
+            notification_warn: Style::default().fg(Color::Magenta),
+            notification_error: Style::default().fg(Color::Red),
+            modal_border: bold.fg(Color::Green),
+            prompt: bold.fg(Color::Reset),
+            hint_key: Style::default().fg(Color::Magenta),
+            hint_header: Style::default().fg(Color::Blue),
+            hint_text: Style::default().fg(Color::Reset),
+        }
+    }
+
+    /// Terminal default colours only, distinguished by modifiers.
+    pub fn mono() -> Self {
+        let plain = Style::default();
+        let bold = plain.bold();
+        Theme {
+            dir: bold,
+            file: plain,
+            selected_dir: bold.reversed(),
+            selected_file: plain.reversed(),
+            matched: plain.underlined(),
+            marked: plain.italic(),
+            marked_selected: plain.italic().reversed(),
+            bookmark: plain,
+            bookmark_selected: plain.reversed(),
+            header_user: bold,
+            header_path: bold,
+            header_file: bold,
+            header_filter: bold.reversed(),
+            tab: plain,
+            tab_active: bold.reversed(),
+            footer_perms: plain,
+            footer_mtime: plain,
+            footer_size: plain,
+            footer_matches: bold,
+            footer_pending: plain,
+            notification_info: plain,
+            notification_success: bold,
+            notification_warn: bold,
+            notification_error: bold.reversed(),
+            modal_border: bold,
+            prompt: bold,
+            hint_key: bold,
+            hint_header: plain.underlined(),
+            hint_text: plain,
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "dark" | "default" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "mono" => Some(Theme::mono()),
+            _ => None,
+        }
+    }
+
+    pub fn from_config(theme: &ThemeConfig) -> io::Result<Self> {
+        let preset = theme.preset.as_deref().unwrap_or("dark");
+        let mut resolved = Theme::preset(preset)
+            .ok_or_else(|| invalid_theme(format!("theme: unknown preset \"{}\"", preset)))?;
+
+        for (slot, style_config) in &theme.styles {
+            let style = resolved
+                .slot_mut(slot)
+                .ok_or_else(|| invalid_theme(format!("theme: unknown style \"{}\"", slot)))?;
+            *style = apply_style(*style, style_config)
+                .map_err(|e| invalid_theme(format!("theme.{}: {}", slot, e)))?;
+        }
+
+        Ok(resolved)
+    }
+
+    fn slot_mut(&mut self, name: &str) -> Option<&mut Style> {
+        let style = match name {
+            "dir" => &mut self.dir,
+            "file" => &mut self.file,
+            "selected_dir" => &mut self.selected_dir,
+            "selected_file" => &mut self.selected_file,
+            "matched" => &mut self.matched,
+            "marked" => &mut self.marked,
+            "marked_selected" => &mut self.marked_selected,
+            "bookmark" => &mut self.bookmark,
+            "bookmark_selected" => &mut self.bookmark_selected,
+            "header_user" => &mut self.header_user,
+            "header_path" => &mut self.header_path,
+            "header_file" => &mut self.header_file,
+            "header_filter" => &mut self.header_filter,
+            "tab" => &mut self.tab,
+            "tab_active" => &mut self.tab_active,
+            "footer_perms" => &mut self.footer_perms,
+            "footer_mtime" => &mut self.footer_mtime,
+            "footer_size" => &mut self.footer_size,
+            "footer_matches" => &mut self.footer_matches,
+            "footer_pending" => &mut self.footer_pending,
+            "notification_info" => &mut self.notification_info,
+            "notification_success" => &mut self.notification_success,
+            "notification_warn" => &mut self.notification_warn,
+            "notification_error" => &mut self.notification_error,
+            "modal_border" => &mut self.modal_border,
+            "prompt" => &mut self.prompt,
+            "hint_key" => &mut self.hint_key,
+            "hint_header" => &mut self.hint_header,
+            "hint_text" => &mut self.hint_text,
+            _ => return None,
+        };
+        Some(style)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+/// Overrides only what the slot sets; `modifiers` replaces the preset's modifiers.
+fn apply_style(base: Style, config: &StyleConfig) -> Result<Style, String> {
+    let mut style = base;
+    if let Some(fg) = &config.fg {
+        style.fg = Some(parse_color(fg)?);
+    }
+    if let Some(bg) = &config.bg {
+        style.bg = Some(parse_color(bg)?);
+    }
+    if let Some(modifiers) = &config.modifiers {
+        let mut add_modifier = Modifier::empty();
+        for name in modifiers {
+            add_modifier |= parse_modifier(name)?;
+        }
+        style.add_modifier = add_modifier;
+        style.sub_modifier = Modifier::empty();
+    }
+    Ok(style)
+}
+
+fn parse_color(value: &str) -> Result<Color, String> {
+    Color::from_str(value).map_err(|_| format!("invalid color \"{}\"", value))
+}
+
+fn parse_modifier(value: &str) -> Result<Modifier, String> {
+    match value.to_lowercase().as_str() {
+        "bold" => Ok(Modifier::BOLD),
+        "dim" => Ok(Modifier::DIM),
+        "italic" => Ok(Modifier::ITALIC),
+        "underlined" | "underline" => Ok(Modifier::UNDERLINED),
+        "slow_blink" => Ok(Modifier::SLOW_BLINK),
+        "rapid_blink" => Ok(Modifier::RAPID_BLINK),
+        "reversed" | "reverse" => Ok(Modifier::REVERSED),
+        "hidden" => Ok(Modifier::HIDDEN),
+        "crossed_out" | "strikethrough" => Ok(Modifier::CROSSED_OUT),
+        _ => Err(format!("invalid modifier \"{}\"", value)),
+    }
+}
+
+fn invalid_theme(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_dark_preset() {
+        let theme = Theme::from_config(&ThemeConfig::default()).unwrap();
+        assert_eq!(theme, Theme::dark());
+    }
+
+    #[test]
+    fn slot_overrides_patch_preset() {
+        let config: ThemeConfig = toml::from_str(
+            r##"
+            preset = "light"
+            selected_file = { bg = "#303030" }
+            dir = { fg = "cyan", modifiers = ["italic", "underlined"] }
+            "##,
+        )
+        .unwrap();
+        let theme = Theme::from_config(&config).unwrap();
+
+        assert_eq!(theme.selected_file.bg, Some(Color::Rgb(0x30, 0x30, 0x30)));
+        assert_eq!(theme.selected_file.fg, Theme::light().selected_file.fg);
+        assert_eq!(theme.dir.fg, Some(Color::Cyan));
+        assert_eq!(
+            theme.dir.add_modifier,
+            Modifier::ITALIC | Modifier::UNDERLINED
+        );
+    }
+
+    #[test]
+    fn invalid_entries_are_errors() {
+        let unknown_slot: ThemeConfig = toml::from_str("nope = { fg = \"red\" }").unwrap();
+        assert!(Theme::from_config(&unknown_slot)
+            .unwrap_err()
+            .to_string()
+            .contains("unknown style \"nope\""));
+
+        let bad_color: ThemeConfig = toml::from_str("dir = { fg = \"blurple\" }").unwrap();
+        assert!(Theme::from_config(&bad_color)
+            .unwrap_err()
+            .to_string()
+            .contains("invalid color"));
+
+        let bad_preset: ThemeConfig = toml::from_str("preset = \"neon\"").unwrap();
+        assert!(Theme::from_config(&bad_preset).is_err());
+    }
+}
@@ -1,10 +1,13 @@
 use fs_extra::dir::{self, CopyOptions};
 use std::{
-    io::{self, stdout},
+    fs::{File, OpenOptions},
+    io::{self, IsTerminal, Read},
     path::{Path, PathBuf},
     process::{Command, Stdio},
 };
 
+use ignore::WalkBuilder;
+
 use crossterm::{
     cursor::Show,
     execute,
@@ -13,10 +16,49 @@ use crossterm::{
 use std::io::Result as IoResult;
 
 use crate::app::{
-    cleanup_terminal,
-    utils::{i18n::Lang, uniquify_path},
+    cleanup_terminal, tui_output,
+    utils::{config_parser::opener::Opener, i18n::Lang, uniquify_path},
 };
 
+pub fn expand_path(input: &str, base: &Path) -> PathBuf {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    match (input.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => base.join(input),
+    }
+}
+
+/// Splits `input` at its last `/` and lists the directories in the first part whose
+/// names start with the second. The directory part is returned as typed.
+pub fn complete_dir_path(input: &str, base: &Path, show_hidden: bool) -> (String, Vec<String>) {
+    let input = if input == "~" { "~/" } else { input };
+    let (dir_part, prefix) = match input.rfind('/') {
+        Some(idx) => input.split_at(idx + 1),
+        None => ("", input),
+    };
+    let dir = if dir_part.is_empty() {
+        base.to_path_buf()
+    } else {
+        expand_path(dir_part, base)
+    };
+
+    let mut candidates: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .filter(|name| name.starts_with(prefix))
+                .filter(|name| show_hidden || prefix.starts_with('.') || !name.starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default();
+    candidates.sort();
+    (dir_part.to_string(), candidates)
+}
+
 pub fn rename_file(full_path: &PathBuf, new_name: String) -> io::Result<()> {
     let parent_dir = full_path
         .parent()
@@ -88,7 +130,7 @@ pub fn paste_file(src_path: &PathBuf, dest_path: &Path) -> io::Result<()> {
         std::fs::copy(src_path, uniq_dest)?;
     } else if src_path.is_dir() {
         let options = CopyOptions::new().overwrite(true).copy_inside(true);
-        let _ = dir::copy(src_path, uniq_dest, &options);
+        dir::copy(src_path, uniq_dest, &options).map_err(io::Error::other)?;
     } else {
         return Err(io::Error::new(
             io::ErrorKind::InvalidInput,
@@ -149,19 +191,27 @@ pub fn whoami_info() -> io::Result<String> {
     Ok(format!("{}@{}", username, hostname))
 }
 
-pub fn exec(program: &String, arg: &[&str]) -> IoResult<()> {
-    let _ = Command::new(program)
+pub fn exec(program: &str, arg: &[&str]) -> IoResult<()> {
+    // with stdout captured, the program draws on the terminal like the interface does
+    let output = if io::stdout().is_terminal() {
+        Stdio::inherit()
+    } else {
+        OpenOptions::new()
+            .write(true)
+            .open("/dev/tty")
+            .map_or_else(|_| Stdio::inherit(), Stdio::from)
+    };
+    Command::new(program)
         .args(arg)
         .stdin(Stdio::inherit())
-        .stdout(Stdio::inherit())
+        .stdout(output)
         .stderr(Stdio::inherit())
-        .status()
-        .expect("couldn't run program");
+        .status()?;
 
     enable_raw_mode()?;
 
     execute!(
-        stdout(),
+        tui_output(),
         EnterAlternateScreen,
         Clear(ClearType::All),
         Show,
@@ -171,6 +221,67 @@ pub fn exec(program: &String, arg: &[&str]) -> IoResult<()> {
     Ok(())
 }
 
+/// Starts a GUI program without handing it the terminal.
+pub fn spawn(program: &str, args: &[String], wait: bool) -> IoResult<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if wait {
+        child.wait()?;
+    } else {
+        // reap it in the background so it does not linger as a zombie
+        std::thread::spawn(move || child.wait());
+    }
+    Ok(())
+}
+
+/// Runs the opener on the files; returns whether it took over the terminal.
+pub fn open_with(opener: &Opener, files: &[PathBuf], editor: &str) -> IoResult<bool> {
+    let (program, args) = opener.command(files, editor);
+    if opener.rule.terminal {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        exec(&program, &args)?;
+        Ok(true)
+    } else {
+        spawn(&program, &args, opener.rule.wait)?;
+        Ok(false)
+    }
+}
+
+/// Walks the tree under `root`, skipping hidden entries unless `show_hidden`
+/// and what ignore files exclude when `respect_ignore`, in a git checkout or not.
+pub fn walker(root: &Path, show_hidden: bool, respect_ignore: bool) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .hidden(!show_hidden)
+        .ignore(respect_ignore)
+        .git_ignore(respect_ignore)
+        .git_global(respect_ignore)
+        .git_exclude(respect_ignore)
+        .parents(respect_ignore)
+        .require_git(false);
+    builder
+}
+
+/// Text is UTF-8 without NUL bytes; only the first KiB is sampled.
+pub fn is_binary_file(file_path: &Path) -> io::Result<bool> {
+    let mut file = File::open(file_path)?;
+    let mut buffer = [0u8; 1024];
+    let bytes_read = file.read(&mut buffer)?;
+    let sample = &buffer[..bytes_read];
+    if sample.contains(&0) {
+        return Ok(true);
+    }
+    Ok(match std::str::from_utf8(sample) {
+        Ok(_) => false,
+        // a character cut off by the end of the sample is still text
+        Err(e) => e.error_len().is_some(),
+    })
+}
+
 #[cfg(unix)]
 pub fn exec_shell_in(dir: &PathBuf) -> io::Result<()> {
     use std::os::unix::process::CommandExt;
@@ -187,3 +298,53 @@ pub fn exec_shell_in(dir: &PathBuf) -> io::Result<()> {
 
     Err(err)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn walker_honours_gitignore_outside_a_checkout() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(".gitignore"), "target\n").unwrap();
+        fs::create_dir(dir.path().join("target")).unwrap();
+        fs::write(dir.path().join("main.rs"), "").unwrap();
+
+        let names = |respect_ignore: bool| {
+            let mut names: Vec<String> = walker(dir.path(), false, respect_ignore)
+                .build()
+                .flatten()
+                .filter(|entry| entry.depth() > 0)
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(true), ["main.rs"]);
+        assert_eq!(names(false), ["main.rs", "target"]);
+    }
+
+    #[test]
+    fn binary_detection_accepts_utf8() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = |name: &str, content: &[u8]| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            is_binary_file(&path).unwrap()
+        };
+
+        assert!(!file("ascii.txt", b"plain text\n"));
+        assert!(!file(
+            "utf8.md",
+            "caf\u{e9} \u{2500}\u{2500} \u{1f600}\n".as_bytes()
+        ));
+        // `é` split by the 1 KiB sample
+        let mut cut = vec![b'a'; 1023];
+        cut.extend("\u{e9}".as_bytes());
+        assert!(!file("cut.txt", &cut));
+        assert!(file("nul.bin", b"text\0more"));
+        assert!(file("latin1.bin", b"caf\xe9 au lait"));
+    }
+}
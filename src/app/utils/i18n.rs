@@ -16,6 +16,33 @@ static ENG: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     );
     map.insert("moved_with_error", "Moved {} items! Failed {} files: {}");
     map.insert("path_does_not_exist", "Path does not exist: {}");
+    map.insert("open_failed", "Failed to open file: {}");
+    map.insert("empty_command", "No command given");
+    map.insert("dual_pane_off", "Dual-pane layout is off");
+    map.insert("last_tab", "Cannot close the last tab");
+    map.insert("no_jumps", "Jump list is empty");
+    map.insert("views_load_failed", "Failed to load view settings: {}");
+    map.insert("views_save_failed", "Failed to save view settings: {}");
+    map.insert("no_frecent_match", "No visited directory matches: {}");
+    map.insert(
+        "frecency_load_failed",
+        "Failed to load visited directories: {}",
+    );
+    map.insert(
+        "frecency_save_failed",
+        "Failed to save visited directories: {}",
+    );
+    map.insert("filter_active", "[filter: {}]");
+    map.insert("invalid_pattern", "Invalid pattern '{}': {}");
+    map.insert(
+        "unknown_engine",
+        "Unknown search engine: {} (substring, prefix, glob, regex, fuzzy)",
+    );
+    map.insert("invalid_find_term", "Invalid find term: {}");
+    map.insert("found", "Found {} entries");
+    map.insert("find_active", "[find: {}]");
+    map.insert("truncated", "{} (truncated)");
+    map.insert("rename_failed", "Failed to rename file: {}");
     map.insert("pasted", "Pasted {} items!");
     map.insert("bookmark_added", "Bookmark added!");
     map.insert("bookmark_deleted", "Bookmark deleted!");
@@ -30,7 +57,13 @@ static ENG: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     map.insert("bookmarks_mode", "--BOOKMARKS--");
     map.insert("no_matches", "No more matches for {}");
     map.insert("matches", "Matches: {}");
-    map.insert("path_does_not_exist", "Path does not exist: {}");
+    map.insert("unknown_command", "Unknown command: {}");
+    map.insert("command_usage", "Usage: {}");
+    map.insert("unknown_option", "Unknown option: {}");
+    map.insert("unknown_sort", "Unknown sort key: {}");
+    map.insert("unclosed_quote", "Missing closing quote");
+    map.insert("bookmark_not_found", "Bookmark not found: {}");
+    map.insert("config_reloaded", "Config reloaded.");
     map
 });
 
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::app::{model::miller::entries::FileKind, utils::config_parser::theme::ThemeConfig};
+
+/// Parsed `LS_COLORS`, e.g. `di=01;34:ln=01;36:ex=01;32:*.tar=01;31`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LsColors {
+    kinds: HashMap<String, Style>,
+    /// `*suffix` globs, lowercased, matched longest first
+    suffixes: Vec<(String, Style)>,
+}
+
+impl LsColors {
+    /// `None` when `theme.ls_colors = false` or the variable is unset.
+    pub fn from_config(theme: &ThemeConfig) -> Option<Self> {
+        if theme.ls_colors == Some(false) {
+            return None;
+        }
+        LsColors::from_env()
+    }
+
+    pub fn from_env() -> Option<Self> {
+        std::env::var("LS_COLORS")
+            .ok()
+            .map(|value| LsColors::parse(&value))
+            .filter(|colors| !colors.is_empty())
+    }
+
+    pub fn parse(value: &str) -> Self {
+        let mut colors = LsColors::default();
+        for entry in value.split(':') {
+            let Some((key, codes)) = entry.split_once('=') else {
+                continue;
+            };
+            // `ln=target` means "colour like the target", which is what we do without it
+            if codes == "target" {
+                continue;
+            }
+            let style = parse_sgr(codes);
+            if let Some(suffix) = key.strip_prefix('*') {
+                colors.suffixes.push((suffix.to_lowercase(), style));
+            } else {
+                colors.kinds.insert(key.to_string(), style);
+            }
+        }
+        // a later duplicate wins, as in GNU ls
+        colors.suffixes.reverse();
+        colors
+            .suffixes
+            .sort_by_key(|(suffix, _)| std::cmp::Reverse(suffix.len()));
+        colors
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty() && self.suffixes.is_empty()
+    }
+
+    /// File-type codes win over extension globs, except for regular files and executables
+    /// without an `ex` code.
+    pub fn style_for(&self, name: &str, kind: FileKind) -> Option<Style> {
+        let code = match kind {
+            FileKind::Regular => None,
+            FileKind::Directory => Some("di"),
+            FileKind::Executable => Some("ex"),
+            FileKind::Symlink => Some("ln"),
+            FileKind::Orphan => Some("or"),
+            FileKind::Pipe => Some("pi"),
+            FileKind::Socket => Some("so"),
+            FileKind::BlockDevice => Some("bd"),
+            FileKind::CharDevice => Some("cd"),
+        };
+        if let Some(style) = code.and_then(|code| self.kinds.get(code)) {
+            return Some(*style);
+        }
+        if matches!(kind, FileKind::Regular | FileKind::Executable) {
+            let name = name.to_lowercase();
+            let by_suffix = self
+                .suffixes
+                .iter()
+                .find(|(suffix, _)| name.ends_with(suffix.as_str()))
+                .map(|(_, style)| *style);
+            return by_suffix.or_else(|| self.kinds.get("fi").copied());
+        }
+        None
+    }
+}
+
+fn parse_sgr(codes: &str) -> Style {
+    let mut style = Style::default();
+    let mut codes = codes.split(';').filter_map(|code| code.parse::<u8>().ok());
+    while let Some(code) = codes.next() {
+        style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            5 => style.add_modifier(Modifier::SLOW_BLINK),
+            6 => style.add_modifier(Modifier::RAPID_BLINK),
+            7 => style.add_modifier(Modifier::REVERSED),
+            8 => style.add_modifier(Modifier::HIDDEN),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            30..=37 => style.fg(Color::Indexed(code - 30)),
+            38 => match extended_color(&mut codes) {
+                Some(color) => style.fg(color),
+                None => style,
+            },
+            39 => style.fg(Color::Reset),
+            40..=47 => style.bg(Color::Indexed(code - 40)),
+            48 => match extended_color(&mut codes) {
+                Some(color) => style.bg(color),
+                None => style,
+            },
+            49 => style.bg(Color::Reset),
+            90..=97 => style.fg(Color::Indexed(code - 90 + 8)),
+            100..=107 => style.bg(Color::Indexed(code - 100 + 8)),
+            _ => style,
+        };
+    }
+    style
+}
+
+/// The tail of `38;5;n` / `38;2;r;g;b` (and the `48` background forms).
+fn extended_color(codes: &mut impl Iterator<Item = u8>) -> Option<Color> {
+    match codes.next()? {
+        5 => codes.next().map(Color::Indexed),
+        2 => Some(Color::Rgb(codes.next()?, codes.next()?, codes.next()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sgr_codes() {
+        assert_eq!(
+            parse_sgr("01;34"),
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Indexed(4))
+        );
+        assert_eq!(
+            parse_sgr("38;5;208;48;2;1;2;3"),
+            Style::default()
+                .fg(Color::Indexed(208))
+                .bg(Color::Rgb(1, 2, 3))
+        );
+        assert_eq!(parse_sgr("91"), Style::default().fg(Color::Indexed(9)));
+    }
+
+    #[test]
+    fn kinds_and_suffixes() {
+        let colors = LsColors::parse("di=34:ln=36:ex=32:or=31:fi=0:*.tar=35:*.tar.gz=33:*.TAR=35");
+        let fg = |name, kind| colors.style_for(name, kind).and_then(|s: Style| s.fg);
+
+        assert_eq!(fg("src", FileKind::Directory), Some(Color::Indexed(4)));
+        assert_eq!(fg("link", FileKind::Symlink), Some(Color::Indexed(6)));
+        assert_eq!(fg("broken", FileKind::Orphan), Some(Color::Indexed(1)));
+        assert_eq!(fg("run.sh", FileKind::Executable), Some(Color::Indexed(2)));
+        assert_eq!(fg("a.tar.gz", FileKind::Regular), Some(Color::Indexed(3)));
+        assert_eq!(fg("B.Tar", FileKind::Regular), Some(Color::Indexed(5)));
+        assert_eq!(
+            colors.style_for("notes", FileKind::Regular),
+            Some(Style::default())
+        );
+        assert_eq!(colors.style_for("fifo", FileKind::Pipe), None);
+    }
+
+    #[test]
+    fn ignores_malformed_entries() {
+        let colors = LsColors::parse("rs=0:garbage:ln=target:di=01;xx;34");
+        assert_eq!(colors.style_for("link", FileKind::Symlink), None);
+        assert_eq!(
+            colors.style_for("d", FileKind::Directory),
+            Some(
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .fg(Color::Indexed(4))
+            )
+        );
+        assert!(LsColors::parse("").is_empty());
+    }
+}
@@ -3,8 +3,11 @@ use std::fs::Permissions;
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
 
+use crate::app::utils::i18n::Lang;
+
 pub mod fs;
 pub mod i18n;
+pub mod ls_colors;
 const KB: f64 = 1024.0;
 const MB: f64 = KB * 1024.0;
 const GB: f64 = MB * 1024.0;
@@ -52,6 +55,65 @@ pub fn permissions_to_string(permissions: &Permissions) -> String {
     result
 }
 
+/// Longest prefix shared by all `candidates`.
+pub fn common_prefix<'s>(candidates: &[&'s str]) -> &'s str {
+    let Some(first) = candidates.first() else {
+        return "";
+    };
+    let len = candidates.iter().fold(first.len(), |len, candidate| {
+        first
+            .char_indices()
+            .zip(candidate.chars())
+            .take_while(|((i, a), b)| *i < len && a == b)
+            .last()
+            .map_or(0, |((i, a), _)| i + a.len_utf8())
+    });
+    &first[..len]
+}
+
+/// Splits a command line into words, honouring quotes and backslash escapes.
+pub fn split_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', q) if q != Some('\'') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                    in_word = true;
+                }
+            }
+            ('"' | '\'', None) => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (c, Some(q)) if c == q => quote = None,
+            (c, None) if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (c, _) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err(Lang::en("unclosed_quote").into());
+    }
+    if in_word {
+        args.push(current);
+    }
+    Ok(args)
+}
+
 fn uniquify_path(path: &Path) -> PathBuf {
     if !path.exists() {
         return path.to_path_buf();
@@ -94,6 +156,15 @@ mod tests {
         assert_eq!(format_bytes(1_234_567_890), "1.15 G");
     }
 
+    #[test]
+    fn split_quoted_args() {
+        assert_eq!(
+            split_args(r#"mkdir "a b" c\ d 'e'"#).unwrap(),
+            vec!["mkdir", "a b", "c d", "e"]
+        );
+        assert!(split_args("cd \"unclosed").is_err());
+    }
+
     #[test]
     fn test_permissions_all() {
         let perms = Permissions::from_mode(0o777);
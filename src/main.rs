@@ -1,12 +1,18 @@
+use clap::Parser;
 use crossterm::{
-    event::EnableMouseCapture,
     execute,
     terminal::{enable_raw_mode, EnterAlternateScreen},
 };
 use ratatui::prelude::*;
-use std::io::{self, stdout};
+use std::io;
 
-use stranger::{app::cleanup_terminal, App};
+use stranger::{
+    app::{
+        cleanup_terminal, tui_output,
+        utils::config_parser::{config_path, shell_init::wrapper, Args, Command},
+    },
+    App,
+};
 
 fn main() -> io::Result<()> {
     std::panic::set_hook(Box::new(|info| {
@@ -14,16 +20,47 @@ fn main() -> io::Result<()> {
         eprintln!("panic: {info}");
     }));
 
+    let args = Args::parse();
+    // answered without starting the interface
+    if let Some(Command::ShellInit { shell }) = args.command {
+        print!("{}", wrapper(shell));
+        return Ok(());
+    }
+    if args.print_config_path {
+        match config_path(&args) {
+            Some(path) => println!("{}", path.display()),
+            None => {
+                eprintln!("Failed to resolve config path: HOME is not set");
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    // config errors must be reported before the alternate screen hides them
+    let mut app = match App::new(args) {
+        Ok(app) => app,
+        Err(e) => {
+            eprintln!("stranger: {e}");
+            std::process::exit(1);
+        }
+    };
+
     enable_raw_mode()?;
-    let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    let mut output = tui_output();
+    execute!(output, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(output);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new()?;
     let run_res = app.run(&mut terminal);
 
     let _ = cleanup_terminal();
 
-    run_res
+    // the one place a failed save is reported, not once per visit
+    if let Err(e) = app.save_frecency() {
+        eprintln!("stranger: {e}");
+    }
+    run_res?;
+    app.write_choice()?;
+    app.write_last_dir()
 }